@@ -1,11 +1,29 @@
-use std::num::NonZeroU32;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, VecDeque},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::checklist::Checklist;
 use crate::{
-    binder::{Binder, BinderSlot},
+    binder::{
+        Alignment, Binder, BinderError, BinderSlot, FillOrder, PageLayout, PageSide, Pocket,
+        Sidedness,
+    },
+    card_id::{CardIdError, IdFormat, Subset},
     card_number::{CardNumber, SlotIndex},
+    collection::{Collection, CollectionSlot, InsertPlan},
+    metadata::{SetList, SetListError, SetListFormat},
+    reorganisation::Reorganisation,
+    search::{search, SearchMatch},
+    set_definition::SetDefinition,
+    set_layout::{PlacedSet, SetLayout},
+    sort_plan::{split_card_list, SortPlan},
 };
-use egui::{Button, Label, Slider, Ui, Vec2, Widget};
-use egui_extras::{Column, TableBuilder};
+use egui::{Button, Color32, Key, Label, Modifiers, RichText, Slider, TextEdit, Ui, Vec2};
+use egui_extras::{Column, TableBody, TableBuilder};
 use egui_flex::{item, Flex};
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -13,16 +31,178 @@ use egui_flex::{item, Flex};
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct App {
     pub value: CardNumber,
-    pub max_value: NonZeroU32,
-    pub binder: Binder,
+    pub collection: Collection,
+    pub sets: SetLayout,
+    /// The 0-based index of the set the card number belongs to.
+    pub selected_set: usize,
+    /// Whether cards located in rapid sort mode are marked as owned.
+    pub mark_sorted_owned: bool,
+    /// The folder holding card images, as `<set name>/<card ID>.png`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub image_folder: String,
+    /// The binder saved before collections were added, moved into `collection` when loaded.
+    #[serde(skip_serializing)]
+    binder: Option<Binder>,
+    /// The card count saved before sets were added, moved into `sets` when loaded.
+    #[serde(skip_serializing)]
+    max_value: Option<NonZeroU32>,
+
+    /// The text typed into the card ID entry.
+    #[serde(skip)]
+    card_id_input: String,
+    /// Why the last card ID entered could not be used.
+    #[serde(skip)]
+    card_id_error: Option<CardIdError>,
+    /// The spread being browsed, or `None` to follow the selected card.
+    #[serde(skip)]
+    spread_cursor: Option<SpreadCursor>,
+    /// The digits typed so far in keyboard number entry.
+    #[serde(skip)]
+    number_entry: String,
+    /// Whether the keyboard shortcut help is shown.
+    #[serde(skip)]
+    show_help: bool,
+    /// The text typed into the rapid sort entry.
+    #[serde(skip)]
+    sort_input: String,
+    /// The most recent rapid sort lookups, newest first.
+    #[serde(skip)]
+    sort_log: VecDeque<SortLookup>,
+    /// The pasted list of cards to plan a pile sort for.
+    #[serde(skip)]
+    pile_input: String,
+    /// The last pile sorting plan.
+    #[serde(skip)]
+    pile_plan: Option<PilePlan>,
+    /// Edited binder layouts by binder number, waiting for the card moves to be reviewed.
+    #[serde(skip)]
+    pending_layouts: BTreeMap<u32, Binder>,
+    /// Why the last pocket could not be reserved.
+    #[serde(skip)]
+    reserve_error: Option<BinderError>,
+    /// The cards to shift to insert a card at the selected card.
+    #[serde(skip)]
+    insert_plan: Option<Result<CardInsertPlan, BinderError>>,
+    /// The path typed into the set list entry.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    set_list_path: String,
+    /// Why the last set list loaded could not be read, or the entries that were not cards.
+    #[serde(skip)]
+    set_list_errors: Vec<String>,
+    /// The text typed into the card search.
+    #[serde(skip)]
+    search_input: String,
+    /// The cards matching the search, best first.
+    #[serde(skip)]
+    search_results: Vec<SearchMatch>,
+    /// Card images dropped onto the window, by set and card ID.
+    #[serde(skip)]
+    dropped_images: BTreeMap<(usize, String), String>,
+    /// The image found for each card looked up so far, if any.
+    #[serde(skip)]
+    card_images: BTreeMap<(usize, CardNumber), Option<String>>,
+    /// The path typed into the checklist export entry.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    checklist_path: String,
+    /// Where the last checklist was written, or why it could not be.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    checklist_status: Option<Result<String, String>>,
+}
+
+/// The number of rapid sort lookups kept in the log.
+const SORT_LOG_LEN: usize = 50;
+
+/// The number of card search matches listed.
+const SEARCH_RESULTS_LEN: usize = 20;
+
+/// The file extensions of card images.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+/// A card entered in rapid sort mode and where it goes.
+#[derive(Debug, PartialEq, Clone)]
+struct SortLookup {
+    /// The label of the card, or the text entered if it was not a card.
+    card: String,
+    /// The binder location of the card, or why it could not be located.
+    location: Result<String, String>,
+}
+
+/// A plan to sort a pile of cards from one set into the binders.
+#[derive(Debug, PartialEq, Clone)]
+struct PilePlan {
+    /// The 0-based index of the set the cards belong to.
+    set: usize,
+    plan: SortPlan,
+    /// The entries that were not cards, with the reason why.
+    invalid: Vec<(String, CardIdError)>,
+}
+
+/// A plan to insert a card into a set, shifting the rest of the set forward.
+#[derive(Debug, PartialEq, Clone)]
+struct CardInsertPlan {
+    plan: InsertPlan,
+    /// The 0-based index of the next set, if the set's last card is pushed into its first pocket.
+    next_set: Option<usize>,
+}
+
+/// Keyboard shortcuts and what they do, shown in the help overlay.
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("← / →", "Previous / next card"),
+    ("Shift + ← / →", "Back / forward 10 cards"),
+    ("Page Up / Page Down", "Previous / next spread"),
+    ("0-9 then Enter", "Go to card number"),
+    ("Backspace / Esc", "Edit / cancel card number"),
+    ("Home", "First card"),
+    ("F1 / ?", "Show or hide this help"),
+];
+
+/// A spread in one of the binders of the collection.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct SpreadCursor {
+    /// The 1-based number of the binder.
+    binder: u32,
+    /// The 0-based spread within the binder.
+    spread: u32,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
             value: CardNumber::try_new(1, 100).expect("Default value should be 1"),
-            max_value: NonZeroU32::new(100).expect("Default value should be 100"),
-            binder: Binder::new(3, 3, 20),
+            collection: Collection::new(Binder::new(3, 3, 20)),
+            sets: SetLayout::default(),
+            selected_set: 0,
+            mark_sorted_owned: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            image_folder: String::new(),
+            binder: None,
+            max_value: None,
+            card_id_input: String::new(),
+            card_id_error: None,
+            spread_cursor: None,
+            number_entry: String::new(),
+            show_help: false,
+            sort_input: String::new(),
+            sort_log: VecDeque::new(),
+            pile_input: String::new(),
+            pile_plan: None,
+            pending_layouts: BTreeMap::new(),
+            reserve_error: None,
+            insert_plan: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            set_list_path: String::new(),
+            set_list_errors: Vec::new(),
+            search_input: String::new(),
+            search_results: Vec::new(),
+            dropped_images: BTreeMap::new(),
+            card_images: BTreeMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            checklist_path: "checklist.pdf".to_owned(),
+            #[cfg(not(target_arch = "wasm32"))]
+            checklist_status: None,
         }
     }
 }
@@ -33,14 +213,468 @@ impl App {
         // This is also where you can customize the look and feel of egui using
         // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
 
+        // Card images are loaded lazily from files and dropped bytes
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
         if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            let mut app: Self = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            app.migrate_legacy();
+            return app;
         }
 
         Default::default()
     }
+
+    /// Move a layout saved with a single binder and card count into the collection and sets.
+    fn migrate_legacy(&mut self) {
+        if let Some(binder) = self.binder.take() {
+            self.collection = Collection::new(binder);
+        }
+
+        if let Some(max_value) = self.max_value.take() {
+            let count = max_value.get();
+            self.sets = SetLayout::new(PlacedSet::new(
+                SetDefinition::new("", count, count),
+                Alignment::Continue,
+            ));
+        }
+    }
+
+    /// The selected set, falling back to the last set if the selection is stale.
+    fn set(&self) -> &SetDefinition {
+        let index = self.selected_set.min(self.sets.len() - 1);
+        self.sets
+            .set(index)
+            .expect("Layout should have a set")
+            .set()
+    }
+
+    /// The largest card number, counting secret rares and every subset.
+    fn max_card(&self) -> u32 {
+        self.set().card_count().max(1)
+    }
+
+    /// Move the selected card by a number of cards, staying put if that would leave the set.
+    fn step_card(&mut self, delta: i32) {
+        self.value = self
+            .value
+            .get()
+            .checked_add_signed(delta)
+            .and_then(|value| CardNumber::try_new(value, self.max_card()))
+            .unwrap_or(self.value);
+    }
+
+    /// Select the first card of the set.
+    fn reset_card(&mut self) {
+        self.value = CardNumber::try_new(1, self.max_card()).expect("Default value should be 1");
+    }
+
+    /// Apply keyboard shortcuts, unless a text field is taking the keyboard.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (mut step, mut spread, mut reset, mut toggle_help) = (0, None, false, false);
+        let mut digits = String::new();
+        let (mut commit, mut cancel, mut backspace) = (false, false, false);
+        ctx.input_mut(|i| {
+            // Shifted arrows first, as unshifted shortcuts also match with shift held
+            for (key, delta) in [(Key::ArrowLeft, -10), (Key::ArrowRight, 10)] {
+                if i.consume_key(Modifiers::SHIFT, key) {
+                    step += delta;
+                }
+            }
+            for (key, delta) in [(Key::ArrowLeft, -1), (Key::ArrowRight, 1)] {
+                if i.consume_key(Modifiers::NONE, key) {
+                    step += delta;
+                }
+            }
+
+            if i.consume_key(Modifiers::NONE, Key::PageUp) {
+                spread = Some(false);
+            }
+            if i.consume_key(Modifiers::NONE, Key::PageDown) {
+                spread = Some(true);
+            }
+
+            reset = i.consume_key(Modifiers::NONE, Key::Home);
+            toggle_help = i.consume_key(Modifiers::NONE, Key::F1)
+                || i.consume_key(Modifiers::NONE, Key::Questionmark);
+            commit = i.consume_key(Modifiers::NONE, Key::Enter);
+            cancel = i.consume_key(Modifiers::NONE, Key::Escape);
+            backspace = i.consume_key(Modifiers::NONE, Key::Backspace);
+
+            for event in &i.events {
+                if let egui::Event::Text(text) = event {
+                    digits.extend(text.chars().filter(char::is_ascii_digit));
+                }
+            }
+        });
+
+        if step != 0 {
+            self.step_card(step);
+        }
+        if let Some(forward) = spread {
+            self.step_spread(forward);
+        }
+        if reset {
+            self.reset_card();
+        }
+        if toggle_help {
+            self.show_help = !self.show_help;
+        }
+
+        // Typed digits build up a card number until Enter selects it
+        self.number_entry.push_str(&digits);
+        if backspace {
+            self.number_entry.pop();
+        }
+        if cancel {
+            self.number_entry.clear();
+        }
+        if commit && !self.number_entry.is_empty() {
+            if let Some(value) = self
+                .number_entry
+                .parse()
+                .ok()
+                .and_then(|value| CardNumber::try_new(value, self.max_card()))
+            {
+                self.value = value;
+            }
+            self.number_entry.clear();
+        }
+    }
+
+    /// Attach a set list to the selected set, keeping any errors to show.
+    fn load_set_list(&mut self, list: Result<SetList, SetListError>) {
+        // The selection may be stale, as it is only clamped when the frame is drawn
+        let index = self.selected_set.min(self.sets.len() - 1);
+        let errors = match list {
+            Ok(list) => self.sets.attach(&self.collection, index, &list),
+            Err(e) => vec![e],
+        };
+
+        // Cards that do not fit are listed with the set instead, as that changes with the binders
+        self.set_list_errors = errors
+            .iter()
+            .filter(|e| !matches!(e, SetListError::DoesNotFit { .. }))
+            .map(ToString::to_string)
+            .collect();
+    }
+
+    /// Find the image of a card, from the images dropped onto the window or the image folder.
+    ///
+    /// Lookups are remembered, so the folder is only searched once for each card.
+    fn card_image(&mut self, set: usize, card: CardNumber) -> Option<String> {
+        if let Some(uri) = self.card_images.get(&(set, card)) {
+            return uri.clone();
+        }
+
+        let definition = self.sets.set(set)?.set();
+        let id = card_id_text(definition, card);
+        let uri = self.dropped_images.get(&(set, id.clone())).cloned();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let uri = uri.or_else(|| {
+            let folder = self.image_folder.trim();
+            if folder.is_empty() {
+                return None;
+            }
+
+            let dir = Path::new(folder).join(set_label(set, definition));
+            IMAGE_EXTENSIONS
+                .iter()
+                .map(|extension| dir.join(format!("{id}.{extension}")))
+                .find(|path| path.is_file())
+                .map(|path| format!("file://{}", path.display()))
+        });
+
+        self.card_images.insert((set, card), uri.clone());
+        uri
+    }
+
+    /// Remove a set, moving the images dropped for the sets after it along with them.
+    fn remove_set(&mut self, index: usize) {
+        if self.sets.remove(index).is_none() {
+            return;
+        }
+
+        self.dropped_images = std::mem::take(&mut self.dropped_images)
+            .into_iter()
+            .filter_map(|((set, id), uri)| match set.cmp(&index) {
+                Ordering::Less => Some(((set, id), uri)),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(((set - 1, id), uri)),
+            })
+            .collect();
+        self.card_images.clear();
+        self.selected_set = index.min(self.sets.len() - 1);
+    }
+
+    /// Use an image dropped onto the window for the card of the selected set named by its file.
+    fn add_dropped_image(&mut self, ctx: &egui::Context, file: &egui::DroppedFile, path: &Path) {
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            return;
+        };
+
+        let uri = match (&file.bytes, &file.path) {
+            (Some(bytes), _) => {
+                // The bytes loader keeps the first image given for a URI, so drop any
+                // earlier image with the same name to show the new one
+                let uri = format!("bytes://{}/{}", self.selected_set, path.display());
+                ctx.forget_image(&uri);
+                ctx.include_bytes(uri.clone(), bytes.clone());
+                uri
+            }
+            (None, Some(path)) => format!("file://{}", path.display()),
+            (None, None) => return,
+        };
+
+        self.dropped_images
+            .insert((self.selected_set, id.to_owned()), uri);
+        self.card_images.clear();
+    }
+
+    /// A card's set name and label, such as "Set 1 #25/165".
+    fn card_label(&self, set: usize, card: CardNumber) -> String {
+        match self.sets.set(set) {
+            Some(placed) => format!(
+                "{} #{}",
+                set_label(set, placed.set()),
+                placed.set().label(card)
+            ),
+            None => format!("#{}", card.get()),
+        }
+    }
+
+    /// The collection with every edited binder layout applied.
+    fn draft_collection(&self) -> Collection {
+        let mut collection = self.collection.clone();
+        for (&number, draft) in &self.pending_layouts {
+            if let Some(binder) = collection.binder_mut(number) {
+                *binder = draft.clone();
+            }
+        }
+
+        collection
+    }
+
+    /// The moves needed for the cards of every set if the edited binder layouts are applied.
+    fn reorganisation(&self) -> Reorganisation {
+        Reorganisation::new(&self.collection, &self.draft_collection(), &self.sets)
+    }
+
+    /// Keep an edited binder layout for review, applying the edits straight away if no cards move.
+    fn edit_layout(&mut self, number: u32, draft: Binder) {
+        if self.collection.binder(number) == Some(&draft) {
+            self.pending_layouts.remove(&number);
+        } else {
+            self.pending_layouts.insert(number, draft);
+        }
+
+        if self.reorganisation().is_empty() {
+            self.apply_layouts();
+        }
+    }
+
+    /// Apply every edited binder layout.
+    fn apply_layouts(&mut self) {
+        self.collection = self.draft_collection();
+        self.pending_layouts.clear();
+    }
+
+    /// Remove a binder, renumbering the edited layouts of the binders after it.
+    fn remove_binder(&mut self, number: u32) {
+        if self.collection.remove(number).is_none() {
+            return;
+        }
+
+        self.pending_layouts = std::mem::take(&mut self.pending_layouts)
+            .into_iter()
+            .filter_map(|(pending, draft)| match pending.cmp(&number) {
+                Ordering::Less => Some((pending, draft)),
+                Ordering::Equal => None,
+                Ordering::Greater => Some((pending - 1, draft)),
+            })
+            .collect();
+    }
+
+    /// The spread holding the selected card.
+    fn selected_spread(&self) -> Option<SpreadCursor> {
+        let location = self
+            .sets
+            .locate(&self.collection, self.selected_set, self.value)
+            .ok()?;
+        let binder = self.collection.binder(location.binder())?;
+        Some(SpreadCursor {
+            binder: location.binder(),
+            spread: binder.spread_of(location.slot().page()),
+        })
+    }
+
+    /// The spread to show, which is the browsed spread if it still exists,
+    /// otherwise the spread holding the selected card.
+    ///
+    /// Falls back to the first spread when the selected card does not fit in the binders,
+    /// so they can still be browsed.
+    fn shown_spread(&self) -> SpreadCursor {
+        self.spread_cursor
+            .filter(|cursor| {
+                self.collection
+                    .binder(cursor.binder)
+                    .is_some_and(|binder| cursor.spread < binder.spreads())
+            })
+            .or_else(|| self.selected_spread())
+            .unwrap_or(SpreadCursor {
+                binder: 1,
+                spread: 0,
+            })
+    }
+
+    /// Browse to the previous or next spread, moving between binders at either end.
+    fn step_spread(&mut self, forward: bool) {
+        let SpreadCursor { binder, spread } = self.shown_spread();
+
+        let cursor = if forward {
+            let spreads = self.collection.binder(binder).map_or(0, Binder::spreads);
+            if spread + 1 < spreads {
+                SpreadCursor {
+                    binder,
+                    spread: spread + 1,
+                }
+            } else if binder < self.collection.len() {
+                SpreadCursor {
+                    binder: binder + 1,
+                    spread: 0,
+                }
+            } else {
+                return;
+            }
+        } else if spread > 0 {
+            SpreadCursor {
+                binder,
+                spread: spread - 1,
+            }
+        } else if let Some(previous) = self.collection.binder(binder - 1) {
+            SpreadCursor {
+                binder: binder - 1,
+                spread: previous.spreads() - 1,
+            }
+        } else {
+            return;
+        };
+
+        self.spread_cursor = Some(cursor);
+    }
+}
+
+/// A single-line text edit that only reports its text once editing finishes,
+/// so that partially typed values are not normalised away.
+fn committed_text_edit(
+    ui: &mut Ui,
+    id_salt: impl std::hash::Hash,
+    current: &str,
+) -> Option<String> {
+    let id = ui.make_persistent_id(id_salt);
+    let mut text = ui
+        .data_mut(|data| data.get_temp::<String>(id))
+        .unwrap_or_else(|| current.to_owned());
+
+    let response = ui.add(TextEdit::singleline(&mut text).desired_width(80.0));
+    if response.lost_focus() {
+        ui.data_mut(|data| data.remove::<String>(id));
+        return Some(text);
+    }
+
+    if response.has_focus() {
+        ui.data_mut(|data| data.insert_temp(id, text));
+    }
+
+    None
+}
+
+fn id_format_rows(body: &mut TableBody<'_>, id_salt: &str, format: &mut IdFormat) {
+    body.row(20.0, |mut row| {
+        row.col(|ui| {
+            ui.label("ID prefix");
+        });
+        row.col(|ui| {
+            if let Some(prefix) = committed_text_edit(ui, (id_salt, "prefix"), format.prefix()) {
+                format.set_prefix(&prefix);
+            }
+        });
+    });
+
+    body.row(20.0, |mut row| {
+        row.col(|ui| {
+            ui.label("ID digits");
+        });
+
+        let mut digits = format.digits();
+        row.col(|ui| {
+            if ui
+                .add(egui::DragValue::new(&mut digits).range(0..=9))
+                .changed()
+            {
+                format.set_digits(digits);
+            }
+        });
+    });
+
+    body.row(20.0, |mut row| {
+        row.col(|ui| {
+            ui.label("ID suffixes");
+        });
+        row.col(|ui| {
+            let current = format.suffixes().join(", ");
+            if let Some(suffixes) = committed_text_edit(ui, (id_salt, "suffixes"), &current) {
+                format.set_suffixes(suffixes.split(','));
+            }
+        });
+    });
+}
+
+fn fill_order_label(fill_order: FillOrder) -> &'static str {
+    match fill_order {
+        FillOrder::RowMajor => "Rows, left to right",
+        FillOrder::RightToLeft => "Rows, right to left",
+        FillOrder::ColumnMajor => "Columns, top to bottom",
+        FillOrder::Serpentine => "Serpentine",
+    }
+}
+
+fn alignment_label(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::Continue => "Continue",
+        Alignment::NewPage => "New page",
+        Alignment::NewSpread => "New spread",
+        Alignment::NewBinder => "New binder",
+    }
+}
+
+/// The fill of a pocket, tinted by whether the card that belongs in it is owned.
+fn pocket_fill(ui: &Ui, owned: bool) -> Color32 {
+    let tint = if owned {
+        Color32::from_rgb(64, 160, 43)
+    } else {
+        Color32::from_rgb(210, 15, 57)
+    };
+
+    ui.visuals()
+        .widgets
+        .inactive
+        .weak_bg_fill
+        .lerp_to_gamma(tint, 0.35)
+}
+
+fn set_label(index: usize, set: &SetDefinition) -> String {
+    match set.name() {
+        "" => format!("Set {}", index + 1),
+        name => name.to_owned(),
+    }
 }
 
 fn settings_table(ui: &mut Ui, app: &mut App) {
@@ -61,138 +695,1162 @@ fn settings_table(ui: &mut Ui, app: &mut App) {
             //     });
             // })
             .body(|mut body| {
+                let mut remove = None;
+
+                let mut edited = None;
+
+                for number in 1..=app.collection.len() {
+                    let can_remove = app.collection.len() > 1;
+                    let Some(current) = app.collection.binder(number) else {
+                        continue;
+                    };
+
+                    // Edits go to a draft, so any cards that move can be shown before applying it
+                    let before = app.pending_layouts.get(&number).unwrap_or(current);
+                    let mut draft = before.clone();
+                    let binder = &mut draft;
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.strong(format!("Binder {number}"));
+                        });
+
+                        row.col(|ui| {
+                            if ui.add_enabled(can_remove, Button::new("Remove")).clicked() {
+                                remove = Some(number);
+                            }
+                        });
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Rows");
+                        });
+
+                        let mut rows = binder.rows();
+                        row.col(|ui| {
+                            if ui
+                                .add(egui::DragValue::new(&mut rows).range(1..=u16::MAX))
+                                .changed()
+                            {
+                                let prev_state = binder.clone();
+                                if binder.set_rows(rows).is_err() {
+                                    *binder = prev_state;
+                                }
+                            }
+                        });
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Columns");
+                        });
+
+                        let mut cols = binder.cols();
+                        row.col(|ui| {
+                            if ui
+                                .add(egui::DragValue::new(&mut cols).range(1..=u16::MAX))
+                                .changed()
+                            {
+                                let prev_state = binder.clone();
+                                if binder.set_cols(cols).is_err() {
+                                    *binder = prev_state;
+                                }
+                            }
+                        });
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Pages");
+                        });
+
+                        let mut pages = binder.pages();
+                        row.col(|ui| {
+                            if ui
+                                .add(egui::DragValue::new(&mut pages).range(1..=u16::MAX))
+                                .changed()
+                            {
+                                let prev_state = binder.clone();
+                                if binder.set_pages(pages).is_err() {
+                                    *binder = prev_state;
+                                }
+                            }
+                        });
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("First page");
+                        });
+
+                        let mut side = binder.first_page_side();
+                        row.col(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(&mut side, PageSide::Left, "Left");
+                                ui.selectable_value(&mut side, PageSide::Right, "Right");
+                            });
+                        });
+                        binder.set_first_page_side(side);
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Sheets");
+                        });
+
+                        let mut sidedness = binder.sidedness();
+                        row.col(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(
+                                    &mut sidedness,
+                                    Sidedness::DoubleSided,
+                                    "Double-sided",
+                                );
+                                ui.selectable_value(
+                                    &mut sidedness,
+                                    Sidedness::SingleSided,
+                                    "Single-sided",
+                                );
+                            });
+                        });
+                        binder.set_sidedness(sidedness);
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Fill order");
+                        });
+
+                        let mut fill_order = binder.fill_order();
+                        row.col(|ui| {
+                            egui::ComboBox::from_id_salt(("fill_order", number))
+                                .selected_text(fill_order_label(fill_order))
+                                .show_ui(ui, |ui| {
+                                    for order in [
+                                        FillOrder::RowMajor,
+                                        FillOrder::RightToLeft,
+                                        FillOrder::ColumnMajor,
+                                        FillOrder::Serpentine,
+                                    ] {
+                                        ui.selectable_value(
+                                            &mut fill_order,
+                                            order,
+                                            fill_order_label(order),
+                                        );
+                                    }
+                                });
+                        });
+                        binder.set_fill_order(fill_order);
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Mirror left pages");
+                        });
+
+                        let mut mirror = binder.mirror_left_pages();
+                        row.col(|ui| {
+                            ui.checkbox(&mut mirror, "");
+                        });
+                        binder.set_mirror_left_pages(mirror);
+                    });
+
+                    let mut remove_layout = None;
+                    for (index, layout) in binder.page_layouts_mut().iter_mut().enumerate() {
+                        body.row(20.0, |mut row| {
+                            let mut first_page = layout.first_page();
+                            let mut last_page = layout.last_page();
+                            row.col(|ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("Pages");
+                                    let first = ui.add(
+                                        egui::DragValue::new(&mut first_page).range(1..=u16::MAX),
+                                    );
+                                    ui.label("to");
+                                    let last = ui.add(
+                                        egui::DragValue::new(&mut last_page).range(1..=u16::MAX),
+                                    );
+
+                                    if first.changed() || last.changed() {
+                                        let prev_state = *layout;
+                                        if layout.set_page_range(first_page, last_page).is_err() {
+                                            *layout = prev_state;
+                                        }
+                                    }
+                                });
+                            });
+
+                            let mut rows = layout.rows();
+                            let mut cols = layout.cols();
+                            row.col(|ui| {
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .add(egui::DragValue::new(&mut rows).range(1..=u16::MAX))
+                                        .changed()
+                                    {
+                                        let prev_state = *layout;
+                                        if layout.set_rows(rows).is_err() {
+                                            *layout = prev_state;
+                                        }
+                                    }
+
+                                    ui.label("×");
+
+                                    if ui
+                                        .add(egui::DragValue::new(&mut cols).range(1..=u16::MAX))
+                                        .changed()
+                                    {
+                                        let prev_state = *layout;
+                                        if layout.set_cols(cols).is_err() {
+                                            *layout = prev_state;
+                                        }
+                                    }
+
+                                    if ui.button("Remove").clicked() {
+                                        remove_layout = Some(index);
+                                    }
+                                });
+                            });
+                        });
+                    }
+
+                    if let Some(index) = remove_layout {
+                        binder.remove_page_layout(index);
+                    }
+
+                    body.row(20.0, |mut row| {
+                        row.col(|_| {});
+                        row.col(|ui| {
+                            if ui.button("Add page layout").clicked() {
+                                // Start with the binder's default layout on its last page
+                                let page = binder.pages();
+                                let layout =
+                                    PageLayout::new(page, page, binder.rows(), binder.cols())
+                                        .expect("Binder layout should be valid");
+                                binder.add_page_layout(layout);
+                            }
+                        });
+                    });
+
+                    if draft != *before {
+                        edited = Some((number, draft));
+                    }
+                }
+
+                if let Some((number, draft)) = edited {
+                    app.edit_layout(number, draft);
+                }
+
+                if let Some(number) = remove {
+                    app.remove_binder(number);
+                }
+
                 body.row(20.0, |mut row| {
+                    row.col(|_| {});
                     row.col(|ui| {
-                        ui.label("Rows");
+                        if ui.button("Add binder").clicked() {
+                            // New binders copy the layout of the last one
+                            let last = app
+                                .collection
+                                .binders()
+                                .last()
+                                .expect("Collection should have a binder")
+                                .clone();
+                            app.collection.push(last);
+                        }
                     });
+                });
+
+                let can_remove_set = app.sets.len() > 1;
+                let selected_set = app.selected_set.min(app.sets.len() - 1);
+                let placed = app
+                    .sets
+                    .set_mut(selected_set)
+                    .expect("Layout should have a set");
+                let mut remove_set = false;
 
-                    let mut rows = app.binder.rows();
+                // Card images are found by set name and card ID
+                let image_names = (
+                    placed.set().name().to_owned(),
+                    placed.set().card_ids().clone(),
+                    placed.set().total(),
+                );
+
+                body.row(20.0, |mut row| {
+                    row.col(|ui| {
+                        ui.strong(format!("Set {}", selected_set + 1));
+                    });
                     row.col(|ui| {
                         if ui
-                            .add(egui::DragValue::new(&mut rows).range(1..=u16::MAX))
-                            .changed()
+                            .add_enabled(can_remove_set, Button::new("Remove"))
+                            .clicked()
                         {
-                            let prev_state = app.binder;
-                            if app.binder.set_rows(rows).is_err() {
-                                app.binder = prev_state;
-                            }
+                            remove_set = true;
+                        }
+                    });
+                });
+
+                body.row(20.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label("Set name");
+                    });
+                    row.col(|ui| {
+                        if let Some(name) =
+                            committed_text_edit(ui, ("set_name", selected_set), placed.set().name())
+                        {
+                            placed.set_mut().set_name(&name);
                         }
                     });
                 });
 
                 body.row(20.0, |mut row| {
                     row.col(|ui| {
-                        ui.label("Columns");
+                        ui.label("Starts on");
+                    });
+
+                    let mut alignment = placed.alignment();
+                    row.col(|ui| {
+                        egui::ComboBox::from_id_salt(("alignment", selected_set))
+                            .selected_text(alignment_label(alignment))
+                            .show_ui(ui, |ui| {
+                                for option in [
+                                    Alignment::Continue,
+                                    Alignment::NewPage,
+                                    Alignment::NewSpread,
+                                    Alignment::NewBinder,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut alignment,
+                                        option,
+                                        alignment_label(option),
+                                    );
+                                }
+                            });
+                    });
+                    placed.set_alignment(alignment);
+                });
+
+                let set = placed.set_mut();
+
+                body.row(20.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label("Printed total");
                     });
 
-                    let mut cols = app.binder.cols();
+                    let mut printed_total = set.printed_total();
                     row.col(|ui| {
                         if ui
-                            .add(egui::DragValue::new(&mut cols).range(1..=u16::MAX))
+                            .add(egui::DragValue::new(&mut printed_total).range(1..=u16::MAX))
                             .changed()
                         {
-                            let prev_state = app.binder;
-                            if app.binder.set_cols(cols).is_err() {
-                                app.binder = prev_state;
-                            }
+                            set.set_printed_total(printed_total);
                         }
                     });
                 });
 
                 body.row(20.0, |mut row| {
                     row.col(|ui| {
-                        ui.label("Pages");
+                        ui.label("Total with secrets");
                     });
 
-                    let mut pages = app.binder.pages();
+                    let mut total = set.total();
                     row.col(|ui| {
                         if ui
-                            .add(egui::DragValue::new(&mut pages).range(1..=u16::MAX))
+                            .add(egui::DragValue::new(&mut total).range(1..=u16::MAX))
                             .changed()
                         {
-                            let prev_state = app.binder;
-                            if app.binder.set_pages(pages).is_err() {
-                                app.binder = prev_state;
-                            }
+                            set.set_total(total);
                         }
                     });
                 });
 
+                id_format_rows(
+                    &mut body,
+                    &format!("main_set_{selected_set}"),
+                    set.card_ids_mut().main_mut(),
+                );
+
+                let mut remove_subset = None;
+                for (index, subset) in set.card_ids_mut().subsets_mut().iter_mut().enumerate() {
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.strong(format!("Subset {}", index + 1));
+                        });
+                        row.col(|ui| {
+                            if ui.button("Remove").clicked() {
+                                remove_subset = Some(index);
+                            }
+                        });
+                    });
+
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label("Cards");
+                        });
+
+                        let mut size = subset.size();
+                        row.col(|ui| {
+                            if ui
+                                .add(egui::DragValue::new(&mut size).range(1..=u16::MAX))
+                                .changed()
+                            {
+                                subset.set_size(size);
+                            }
+                        });
+                    });
+
+                    id_format_rows(
+                        &mut body,
+                        &format!("subset_{selected_set}_{index}"),
+                        subset.format_mut(),
+                    );
+                }
+
+                if let Some(index) = remove_subset {
+                    set.card_ids_mut().subsets_mut().remove(index);
+                }
+
                 body.row(20.0, |mut row| {
+                    row.col(|_| {});
                     row.col(|ui| {
-                        ui.label("Max Value");
+                        if ui.button("Add subset").clicked() {
+                            set.card_ids_mut().subsets_mut().push(Subset::new("TG", 30));
+                        }
                     });
+                });
+
+                if (set.name(), set.card_ids(), set.total())
+                    != (image_names.0.as_str(), &image_names.1, image_names.2)
+                {
+                    app.card_images.clear();
+                }
+
+                if remove_set {
+                    app.remove_set(selected_set);
+                }
+
+                body.row(20.0, |mut row| {
+                    row.col(|_| {});
                     row.col(|ui| {
-                        ui.add(egui::DragValue::new(&mut app.max_value).range(1..=u16::MAX));
+                        if ui.button("Add set").clicked() {
+                            // New sets start on a fresh page after the last one
+                            app.sets
+                                .push(PlacedSet::new(SetDefinition::default(), Alignment::NewPage));
+                            app.selected_set = app.sets.len() - 1;
+                        }
                     });
                 });
             });
     });
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CardTable {
-    Left,
-    Right,
-}
+fn rapid_sort(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Rapid sort", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Card ID:");
 
-fn card_table(id: &str, ui: &mut Ui, app: &mut App, page_side: CardTable) {
-    let card_ratio = Vec2::new(2.5, 3.5);
+            let response = ui.add(
+                TextEdit::singleline(&mut app.sort_input).hint_text("Type a card, press Enter"),
+            );
 
-    let binder_rows = app.binder.rows();
-    let binder_cols = app.binder.cols();
-
-    TableBuilder::new(ui)
-        .id_salt(id)
-        .vscroll(false)
-        .columns(Column::auto().resizable(false), binder_cols as usize)
-        .body(|body| {
-            body.rows(20.0, binder_rows as usize, |mut row| {
-                for col in 0..binder_cols {
-                    let page_offset = app.value.to_index().get() / (binder_rows * binder_cols);
-                    let current_cell_index = SlotIndex::new(
-                        (row.index() as u32 * binder_cols + col)
-                            + page_offset * binder_rows * binder_cols,
-                    );
+            if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                let input = app.sort_input.trim().to_owned();
+                app.sort_input.clear();
+                if !input.is_empty() {
+                    let lookup = sort_lookup(app, &input);
+                    app.sort_log.push_front(lookup);
+                    app.sort_log.truncate(SORT_LOG_LEN);
+                }
+
+                // Stay in the entry box, ready for the next card
+                response.request_focus();
+            }
+
+            ui.checkbox(&mut app.mark_sorted_owned, "Mark as owned");
 
-                    // Check if the current slot is the selected one
-                    // The selected slot is the one that matches the current card number
-                    // and is on the correct page
-                    let current_slot = BinderSlot::from_card_number(&app.binder, app.value);
-
-                    // Check if the current user-selected card slot is on the correct page
-                    let correct_page = match page_side {
-                        // Page 1 starts on the left side, Page 2 starts on the right side,
-                        // so the modulo seems to be inverted
-                        CardTable::Left => page_offset % 2 == 1,
-                        CardTable::Right => page_offset % 2 == 0,
+            if ui.button("Clear log").clicked() {
+                app.sort_log.clear();
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .max_height(150.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for lookup in &app.sort_log {
+                    match &lookup.location {
+                        Ok(location) => ui.label(format!("#{}: {location}", lookup.card)),
+                        Err(e) => ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            format!("{}: {e}", lookup.card),
+                        ),
                     };
+                }
+            });
+    });
+}
 
-                    let current_slot_selected =
-                        correct_page && current_slot.index() == current_cell_index;
+fn pile_planner(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Pile sorting", |ui| {
+        ui.add(
+            TextEdit::multiline(&mut app.pile_input)
+                .hint_text("Paste card IDs, separated by spaces, commas or new lines")
+                .desired_rows(3),
+        );
 
-                    let dbg_label = format!(
-                        "Row: {}, Col: {}\nPage: {}\nCorrect page: {}\nSelected: {}",
-                        row.index(),
-                        col,
-                        current_slot.page(),
-                        correct_page,
-                        current_slot_selected
-                    );
+        ui.horizontal(|ui| {
+            if ui.button("Plan").clicked() {
+                let mut invalid = Vec::new();
+                let mut locations = Vec::new();
+                for token in split_card_list(&app.pile_input) {
+                    match app.set().parse(token) {
+                        Ok(card) => locations.push((
+                            card,
+                            app.sets.locate(&app.collection, app.selected_set, card),
+                        )),
+                        Err(e) => invalid.push((token.to_owned(), e)),
+                    }
+                }
 
-                    let dbg_label = cfg!(debug_assertions)
-                        .then(|| dbg_label)
-                        .unwrap_or_default();
+                let plan = SortPlan::from_locations(&app.collection, locations);
+                app.pile_plan = Some(PilePlan {
+                    set: app.selected_set,
+                    plan,
+                    invalid,
+                });
+            }
 
-                    row.col(|ui| {
-                        Button::new(dbg_label)
-                            .min_size(card_ratio * 20.0)
-                            .selected(current_slot_selected)
-                            .ui(ui);
+            if ui.button("Clear").clicked() {
+                app.pile_input.clear();
+                app.pile_plan = None;
+            }
+        });
+
+        let Some(PilePlan { set, plan, invalid }) = &app.pile_plan else {
+            return;
+        };
+
+        // Label cards by their ID, falling back to the card number if the set is gone
+        let label = |card: CardNumber| {
+            app.sets
+                .set(*set)
+                .map_or_else(|| card.get().to_string(), |placed| placed.set().label(card))
+        };
+
+        for (step, group) in plan.groups().iter().enumerate() {
+            ui.label(format!("{}. {}", step + 1, group.describe(label)));
+        }
+        for (card, e) in plan.unplaced() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("Card {} does not fit: {e}", label(*card)),
+            );
+        }
+        for (token, e) in invalid {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("{token}: {e}"));
+        }
+    });
+}
+
+fn set_list_loader(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Set list", |ui| {
+        ui.label(format!(
+            "Load card names, rarities and types for {} from a .json or .csv file, \
+             or drop one onto the window.",
+            set_label(app.selected_set, app.set())
+        ));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label("File:");
+            ui.text_edit_singleline(&mut app.set_list_path);
+
+            if ui.button("Load").clicked() {
+                let list = SetList::read(Path::new(app.set_list_path.trim()));
+                app.load_set_list(list);
+            }
+        });
+
+        ui.label(format!(
+            "{} of {} cards have set list entries",
+            app.set().card_infos().count(),
+            app.set().card_count()
+        ));
+
+        for e in &app.set_list_errors {
+            ui.colored_label(ui.visuals().warn_fg_color, e);
+        }
+        for (card, e) in app.sets.unplaced_cards(&app.collection, app.selected_set) {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("Card {} does not fit: {e}", app.set().label(card)),
+            );
+        }
+    });
+}
+
+/// The card ID of a card, such as "025" or "TG05", falling back to the card number.
+fn card_id_text(set: &SetDefinition, card: CardNumber) -> String {
+    set.card_id(card)
+        .map_or_else(|| card.get().to_string(), |id| id.to_string())
+}
+
+/// Read a set list dropped onto the window, from its contents on the web or its path natively.
+fn read_dropped_set_list(file: &egui::DroppedFile) -> Result<SetList, SetListError> {
+    match (&file.bytes, &file.path) {
+        (Some(bytes), _) => SetList::parse(
+            &String::from_utf8_lossy(bytes),
+            SetListFormat::from_path(Path::new(&file.name))?,
+        ),
+        (None, Some(path)) => SetList::read(path),
+        (None, None) => Err(SetListError::UnknownFormat(file.name.clone())),
+    }
+}
+
+fn card_image_settings(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Card images", |ui| {
+        ui.label(
+            "Card images are shown in their pockets, named by set and card ID, \
+             such as \"Scarlet & Violet/025.png\". PNG and JPEG images are supported.",
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label("Image folder:");
+            if ui.text_edit_singleline(&mut app.image_folder).changed() {
+                app.card_images.clear();
+            }
+
+            if ui.button("Reload").clicked() {
+                app.card_images.clear();
+                ui.ctx().forget_all_images();
+            }
+        });
+
+        ui.label(format!(
+            "Drop images named by card ID, such as \"025.png\", onto the window to use them for {}.",
+            set_label(app.selected_set, app.set())
+        ));
+
+        if !app.dropped_images.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} images dropped", app.dropped_images.len()));
+                if ui.button("Forget dropped images").clicked() {
+                    app.dropped_images.clear();
+                    app.card_images.clear();
+                }
+            });
+        }
+    });
+}
+
+fn checklist_export(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Print checklist", |ui| {
+        let pages: u32 = app.collection.binders().iter().map(Binder::pages).sum();
+        ui.label(format!(
+            "Export all {pages} binder pages with the card in each pocket and a box to tick \
+             when it is owned, ready to print as SVG or PDF."
+        ));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.add(TextEdit::singleline(&mut app.checklist_path).hint_text("checklist.pdf"));
+
+                if ui.button("Export").clicked() {
+                    let path = PathBuf::from(app.checklist_path.trim());
+                    let status = Checklist::new(&app.collection, &app.sets)
+                        .map_err(|e| e.to_string())
+                        .and_then(|checklist| {
+                            checklist.write(&path).map_err(|e| e.to_string())?;
+                            Ok(format!(
+                                "Wrote {} pages to {}",
+                                checklist.pages().len(),
+                                path.display()
+                            ))
+                        });
+                    app.checklist_status = Some(status);
+                }
+            });
+
+            match &app.checklist_status {
+                Some(Ok(message)) => {
+                    ui.label(message);
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(ui.visuals().warn_fg_color, e);
+                }
+                None => {}
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        ui.weak("Exporting needs the desktop app, or its checklist command.");
+    });
+}
+
+fn insert_planner(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Insert a card", |ui| {
+        ui.label("Insert a new card at the selected card, shifting the rest of the set forward.");
+
+        ui.horizontal(|ui| {
+            if ui
+                .button(format!("Plan insert at #{}", app.set().label(app.value)))
+                .clicked()
+            {
+                let card_count = app.set().card_count();
+                let plan = app
+                    .sets
+                    .locate(&app.collection, app.selected_set, app.value)
+                    .and_then(|at| {
+                        // The set runs from its first card to its last
+                        let start = at.index().get() - app.value.to_index().get();
+                        let end = start.checked_add(card_count).ok_or(BinderError::Overflow)?;
+                        Ok(CardInsertPlan {
+                            plan: app
+                                .collection
+                                .plan_insert(at.index(), SlotIndex::new(end))?,
+                            next_set: app
+                                .sets
+                                .insert_collision(&app.collection, app.selected_set)?,
+                        })
                     });
+                app.insert_plan = Some(plan);
+            }
+
+            if ui.button("Clear").clicked() {
+                app.insert_plan = None;
+            }
+        });
+
+        let CardInsertPlan { plan, next_set } = match &app.insert_plan {
+            Some(Ok(plan)) => plan,
+            Some(Err(e)) => {
+                ui.colored_label(ui.visuals().warn_fg_color, e.to_string());
+                return;
+            }
+            None => return,
+        };
+
+        let describe = |slot: CollectionSlot| {
+            let binder = app
+                .collection
+                .binder(slot.binder())
+                .expect("Located binder should exist");
+            format!(
+                "binder {}, page {}, slot {}",
+                slot.binder(),
+                slot.slot().page(),
+                slot.slot().slot_on_page(binder)
+            )
+        };
+
+        let overflow = plan.overflow().count();
+        if overflow > 0 {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("{overflow} cards are pushed past the last page of the last binder"),
+            );
+        }
+
+        // The next set's first card is in the pocket the set's last card moves into
+        if let Some((next, placed)) = next_set.and_then(|next| Some((next, app.sets.set(next)?))) {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "The last card is pushed into the first pocket of {}, which has to move too",
+                    set_label(next, placed.set())
+                ),
+            );
+        }
+
+        let crossing = plan
+            .shifts()
+            .iter()
+            .filter(|shift| {
+                shift
+                    .to()
+                    .is_some_and(|to| to.binder() != shift.from().binder())
+            })
+            .count();
+        if crossing > 0 {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("{crossing} cards are pushed past the last page into the next binder"),
+            );
+        }
+
+        ui.label(format!("The new card goes in {}", describe(plan.at())));
+
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for shift in plan.shifts() {
+                    let card = match app.sets.card_at(&app.collection, shift.from().index()) {
+                        Ok(Some((set, card))) => app
+                            .sets
+                            .set(set)
+                            .map(|placed| placed.set().label(card))
+                            .unwrap_or_default(),
+                        _ => shift.from().card_number().get().to_string(),
+                    };
+
+                    let to = shift
+                        .to()
+                        .map_or_else(|| "out of the binders".to_owned(), describe);
+                    ui.label(format!("#{card}: {} → {to}", describe(shift.from())));
                 }
             });
+    });
+}
+
+/// Describe a card location, such as "Binder 1, page 3, slot 7".
+fn location_label(collection: &Collection, location: CollectionSlot) -> String {
+    let binder = collection
+        .binder(location.binder())
+        .expect("Located binder should exist");
+    format!(
+        "Binder {}, page {}, slot {}",
+        location.binder(),
+        location.slot().page(),
+        location.slot().slot_on_page(binder)
+    )
+}
+
+fn card_search(ui: &mut Ui, app: &mut App) {
+    ui.collapsing("Search cards", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Search:");
+
+            let response = ui
+                .add(TextEdit::singleline(&mut app.search_input).hint_text("Card name or number"));
+            if response.changed() {
+                app.search_results = search(&app.sets, &app.search_input);
+            }
+
+            if ui.button("Clear").clicked() {
+                app.search_input.clear();
+                app.search_results.clear();
+            }
         });
+
+        let mut select = None;
+        for found in app.search_results.iter().take(SEARCH_RESULTS_LEN) {
+            let Some(placed) = app.sets.set(found.set()) else {
+                continue;
+            };
+
+            let set = placed.set();
+            let card = match set.card_info(found.card()) {
+                Some(info) => format!("#{} {info}", set.label(found.card())),
+                None => format!("#{}", set.label(found.card())),
+            };
+            let location = match app.sets.locate(&app.collection, found.set(), found.card()) {
+                Ok(location) => location_label(&app.collection, location),
+                Err(e) => format!("does not fit: {e}"),
+            };
+
+            let text = format!("{card} ({}): {location}", set_label(found.set(), set));
+            if ui.selectable_label(false, text).clicked() {
+                select = Some((found.set(), found.card()));
+            }
+        }
+
+        if app.search_results.len() > SEARCH_RESULTS_LEN {
+            ui.weak(format!(
+                "{} more matches",
+                app.search_results.len() - SEARCH_RESULTS_LEN
+            ));
+        } else if app.search_results.is_empty() && !app.search_input.trim().is_empty() {
+            ui.weak("No cards match");
+        }
+
+        // Selecting a match brings its spread into view
+        if let Some((set, card)) = select {
+            app.selected_set = set;
+            app.value = card;
+            app.spread_cursor = None;
+        }
+    });
+}
+
+/// Locate a card entered in rapid sort mode, selecting it so it is highlighted in the spread.
+fn sort_lookup(app: &mut App, input: &str) -> SortLookup {
+    let card_number = match app.set().parse(input) {
+        Ok(card_number) => card_number,
+        Err(e) => {
+            return SortLookup {
+                card: input.to_owned(),
+                location: Err(e.to_string()),
+            }
+        }
+    };
+
+    let card = app.set().label(card_number);
+    let location = match app
+        .sets
+        .locate(&app.collection, app.selected_set, card_number)
+    {
+        Ok(location) => location,
+        Err(e) => {
+            return SortLookup {
+                card,
+                location: Err(e.to_string()),
+            }
+        }
+    };
+
+    app.value = card_number;
+    app.spread_cursor = None;
+    if app.mark_sorted_owned {
+        if let Some(placed) = app.sets.set_mut(app.selected_set) {
+            let ownership = placed.ownership_mut();
+            ownership.set_count(card_number, ownership.count(card_number) + 1);
+        }
+    }
+
+    SortLookup {
+        card,
+        location: Ok(location_label(&app.collection, location)),
+    }
+}
+
+fn card_table(id: &str, ui: &mut Ui, app: &mut App, cursor: SpreadCursor, page_side: PageSide) {
+    let card_ratio = Vec2::new(2.5, 3.5);
+
+    let Some(binder) = app.collection.binder(cursor.binder).cloned() else {
+        return;
+    };
+
+    // Reserving pockets edits the layout, so it goes through the binder's draft if it has one
+    let draft = app
+        .pending_layouts
+        .get(&cursor.binder)
+        .unwrap_or(&binder)
+        .clone();
+
+    // The selected card's pocket, if it is in the binder being shown
+    let current_slot = app
+        .sets
+        .locate(&app.collection, app.selected_set, app.value)
+        .ok()
+        .filter(|location| location.binder() == cursor.binder)
+        .map(|location| location.slot());
+
+    // The page on this side of the spread, if any.
+    // Sides without a page are the unused back of a single-sided sheet,
+    // or lie before the first or after the last page.
+    let page = binder.spread_page(cursor.spread, page_side);
+
+    // Each page is drawn with its own pocket layout
+    let (binder_rows, binder_cols) = match page {
+        Some(page) => (binder.rows_on(page), binder.cols_on(page)),
+        None => (binder.rows(), binder.cols()),
+    };
+
+    // The set and card that belong in each pocket on the page, row by row
+    let cards: Vec<Option<(SlotIndex, usize, CardNumber)>> = (0..binder_rows)
+        .flat_map(|row| (0..binder_cols).map(move |col| (row + 1, col + 1)))
+        .map(|(row, col)| {
+            let slot = app
+                .collection
+                .from_position(cursor.binder, page?, row, col)
+                .ok()?;
+            let (set, card) = app.sets.card_at(&app.collection, slot.index()).ok()??;
+            Some((slot.index(), set, card))
+        })
+        .collect();
+
+    // The image of each card on the page, loaded when first drawn
+    let images: Vec<Option<String>> = cards
+        .iter()
+        .map(|card| {
+            let (_, set, card) = (*card)?;
+            app.card_image(set, card)
+        })
+        .collect();
+
+    // The card identifier, such as "025" or "TG05", falling back to the card number
+    let card_id = |set: usize, card: CardNumber| {
+        app.sets.set(set).map_or_else(
+            || card.get().to_string(),
+            |placed| card_id_text(placed.set(), card),
+        )
+    };
+
+    let mut toggle_reserved = None;
+    let mut toggle_owned = None;
+    let mut select = None;
+
+    ui.vertical(|ui| {
+        let first = cards.iter().flatten().min_by_key(|(index, _, _)| *index);
+        let last = cards.iter().flatten().max_by_key(|(index, _, _)| *index);
+        match (page, first, last) {
+            (Some(page), Some(&(_, first_set, first)), Some(&(_, last_set, last))) => {
+                ui.label(format!(
+                    "Page {page}: #{}–#{}",
+                    card_id(first_set, first),
+                    card_id(last_set, last)
+                ))
+            }
+            (Some(page), _, _) => ui.label(format!("Page {page}")),
+            (None, _, _) => ui.weak("Unused"),
+        };
+
+        TableBuilder::new(ui)
+            .id_salt((id, binder_rows, binder_cols))
+            .vscroll(false)
+            .columns(Column::auto().resizable(false), binder_cols as usize)
+            .body(|body| {
+                body.rows(20.0, binder_rows as usize, |mut row| {
+                    for col in 0..binder_cols {
+                        let pocket =
+                            page.map(|page| Pocket::new(page, row.index() as u32 + 1, col + 1));
+                        let reserved = pocket.is_some_and(|pocket| binder.is_reserved(pocket));
+
+                        // Map the cell back through the binder's fill order
+                        let current_cell_index = pocket
+                            .and_then(|pocket| {
+                                BinderSlot::from_position(
+                                    &binder,
+                                    pocket.page(),
+                                    pocket.row(),
+                                    pocket.col(),
+                                )
+                                .ok()
+                            })
+                            .map(|slot| slot.index());
+
+                        // The selected slot is the one that matches the current card number
+                        let current_slot_selected = current_cell_index.is_some()
+                            && current_cell_index == current_slot.map(|slot| slot.index());
+
+                        let cell = row.index() * binder_cols as usize + col as usize;
+                        let card = cards[cell].map(|(_, set, card)| (set, card));
+                        let owned = card.and_then(|(set, card)| {
+                            Some(app.sets.set(set)?.ownership().is_owned(card))
+                        });
+
+                        let label = match card {
+                            _ if reserved => RichText::new("Reserved").weak(),
+                            Some((set, card)) => {
+                                let info = app
+                                    .sets
+                                    .set(set)
+                                    .and_then(|placed| placed.set().card_info(card));
+                                match info {
+                                    Some(info) => {
+                                        RichText::new(format!("#{}\n{info}", card_id(set, card)))
+                                    }
+                                    None => RichText::new(format!("#{}", card_id(set, card))),
+                                }
+                            }
+                            None => RichText::new(""),
+                        };
+
+                        let image = images[cell].clone().filter(|_| !reserved);
+
+                        row.col(|ui| {
+                            let size = card_ratio * 20.0;
+                            let mut button = match &image {
+                                Some(uri) => {
+                                    // Missing cards are greyed out
+                                    let tint = if owned == Some(false) {
+                                        Color32::from_gray(80)
+                                    } else {
+                                        Color32::WHITE
+                                    };
+                                    let thumbnail = egui::Image::new(uri.as_str())
+                                        .fit_to_exact_size(size - ui.spacing().button_padding * 2.0)
+                                        .tint(tint);
+                                    Button::image(thumbnail)
+                                }
+                                None => Button::new(label.clone()).wrap(),
+                            }
+                            .min_size(size)
+                            .selected(current_slot_selected);
+                            if let Some(owned) = owned {
+                                button = button.fill(pocket_fill(ui, owned));
+                            }
+
+                            // Clicking selects the card in the pocket, double-clicking also
+                            // marks it as owned or missing
+                            let mut response = ui.add_enabled(page.is_some(), button);
+                            if image.is_some() {
+                                response = response.on_hover_text(label.text());
+                            }
+                            if response.clicked() {
+                                select = card;
+                            }
+                            if response.double_clicked() {
+                                toggle_owned = card;
+                            }
+
+                            if let Some(pocket) = pocket {
+                                response.context_menu(|ui| {
+                                    if let Some(owned) = owned {
+                                        let text = if owned {
+                                            "Mark as missing"
+                                        } else {
+                                            "Mark as owned"
+                                        };
+
+                                        if ui.button(text).clicked() {
+                                            toggle_owned = card;
+                                            ui.close_menu();
+                                        }
+                                    }
+
+                                    let text = if draft.is_reserved(pocket) {
+                                        "Release pocket"
+                                    } else {
+                                        "Reserve pocket"
+                                    };
+
+                                    if ui.button(text).clicked() {
+                                        toggle_reserved = Some(pocket);
+                                        ui.close_menu();
+                                    }
+                                });
+                            }
+                        });
+                    }
+                });
+            });
+    });
+
+    if let Some((set, card)) = select {
+        app.selected_set = set;
+        app.value = card;
+    }
+
+    if let Some((set, card)) = toggle_owned {
+        if let Some(placed) = app.sets.set_mut(set) {
+            placed.ownership_mut().toggle(card);
+        }
+    }
+
+    // Numbering flows around reserved pockets, so toggling one moves every later card
+    // and the moves are reviewed like any other layout edit
+    if let Some(pocket) = toggle_reserved {
+        let mut draft = draft;
+        let toggled = if draft.unreserve(pocket) {
+            Ok(())
+        } else {
+            draft.reserve(pocket)
+        };
+
+        match toggled {
+            Ok(()) => {
+                app.reserve_error = None;
+                app.edit_layout(cursor.binder, draft);
+            }
+            Err(e) => app.reserve_error = Some(e),
+        }
+    }
 }
 
 impl eframe::App for App {
@@ -221,9 +1879,91 @@ impl eframe::App for App {
                 }
 
                 egui::widgets::global_theme_preference_buttons(ui);
+                ui.add_space(16.0);
+
+                if ui.button("Keyboard shortcuts").clicked() {
+                    self.show_help = !self.show_help;
+                }
             });
         });
 
+        // Dropped images are card images, anything else is read as a set list
+        for file in ctx.input(|i| i.raw.dropped_files.clone()) {
+            let path = file
+                .path
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(&file.name));
+            let is_image = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+                });
+
+            if is_image {
+                self.add_dropped_image(ctx, &file, &path);
+            } else {
+                let list = read_dropped_set_list(&file);
+                self.load_set_list(list);
+            }
+        }
+
+        if !self.pending_layouts.is_empty() {
+            let reorganisation = self.reorganisation();
+            let numbers: Vec<String> = self
+                .pending_layouts
+                .keys()
+                .map(ToString::to_string)
+                .collect();
+            let title = match numbers.as_slice() {
+                [number] => format!("Reorganise binder {number}"),
+                _ => format!("Reorganise binders {}", numbers.join(", ")),
+            };
+            let (mut apply, mut discard) = (false, false);
+
+            egui::Window::new(title)
+                .id(egui::Id::new("reorganise"))
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label("These moves are needed before the new layout can be used:");
+
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            for (step, card_move) in reorganisation.moves().iter().enumerate() {
+                                let card = self.card_label(card_move.set(), card_move.card());
+                                ui.label(format!("{}. {}", step + 1, card_move.describe(&card)));
+                            }
+                        });
+
+                    ui.horizontal(|ui| {
+                        apply = ui.button("Apply layout").clicked();
+                        discard = ui.button("Discard changes").clicked();
+                    });
+                });
+
+            if apply {
+                self.apply_layouts();
+            }
+            if discard {
+                self.pending_layouts.clear();
+            }
+        }
+
+        egui::Window::new("Keyboard shortcuts")
+            .open(&mut self.show_help)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts").striped(true).show(ui, |ui| {
+                    for (keys, action) in SHORTCUTS {
+                        ui.strong(*keys);
+                        ui.label(*action);
+                        ui.end_row();
+                    }
+                });
+            });
+
         egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
                 use egui::special_emojis::GITHUB;
@@ -239,31 +1979,108 @@ impl eframe::App for App {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("TCG Bound");
 
+            // Keep the selection valid after sets are removed or resized
+            self.selected_set = self.selected_set.min(self.sets.len() - 1);
+            if self.value.get() > self.max_card() {
+                self.value = CardNumber::try_new(self.max_card(), self.max_card())
+                    .expect("Largest card should be valid");
+            }
+
             settings_table(ui, self);
+            card_search(ui, self);
+            rapid_sort(ui, self);
+            pile_planner(ui, self);
+            insert_planner(ui, self);
+            set_list_loader(ui, self);
+            card_image_settings(ui, self);
+            checklist_export(ui, self);
 
             ui.separator();
 
+            let selected = (self.selected_set, self.value);
+            self.handle_shortcuts(ctx);
+
             Flex::vertical().grow_items(1.0).show(ui, |flex| {
+                flex.add_ui(item(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Set:");
+
+                        let sets = &self.sets;
+                        let mut selected_set = self.selected_set;
+                        egui::ComboBox::from_id_salt("selected_set")
+                            .selected_text(set_label(selected_set, self.set()))
+                            .show_ui(ui, |ui| {
+                                for (index, placed) in sets.sets().iter().enumerate() {
+                                    ui.selectable_value(
+                                        &mut selected_set,
+                                        index,
+                                        set_label(index, placed.set()),
+                                    );
+                                }
+                            });
+
+                        if selected_set != self.selected_set {
+                            self.selected_set = selected_set;
+                            self.value = CardNumber::try_new(1, self.max_card())
+                                .expect("Default value should be 1");
+                        }
+                    });
+                });
+
                 flex.add(
                     item().shrink().align_self(egui_flex::FlexAlign::Start),
-                    Label::new("Card Number:"),
+                    Label::new(format!("Card Number: {}", self.set().label(self.value))),
                 );
 
+                if !self.number_entry.is_empty() {
+                    flex.add(
+                        item().shrink().align_self(egui_flex::FlexAlign::Start),
+                        Label::new(
+                            RichText::new(format!("Go to card: {}_", self.number_entry)).strong(),
+                        ),
+                    );
+                }
+
                 let slider_width = 100.0;
                 let mut slider_value = self.value.get();
                 if flex
                     .add(
                         item().grow(1.0).min_width(slider_width),
-                        Slider::new(&mut slider_value, 1..=self.max_value.get()),
+                        Slider::new(&mut slider_value, 1..=self.max_card()),
                     )
                     .changed()
                 {
-                    if let Some(new_value) = CardNumber::try_new(slider_value, self.max_value.get())
-                    {
+                    if let Some(new_value) = CardNumber::try_new(slider_value, self.max_card()) {
                         self.value = new_value;
                     }
                 }
 
+                flex.add_ui(item(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Card ID:");
+
+                        let response = ui.add(
+                            TextEdit::singleline(&mut self.card_id_input)
+                                .hint_text("e.g. 25, TG05, 123a"),
+                        );
+
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            match self.set().parse(&self.card_id_input) {
+                                Ok(card_number) => {
+                                    self.value = card_number;
+                                    self.card_id_input.clear();
+                                    self.card_id_error = None;
+                                }
+                                Err(e) => self.card_id_error = Some(e),
+                            }
+                        }
+
+                        if let Some(e) = &self.card_id_error {
+                            ui.colored_label(ui.visuals().warn_fg_color, e.to_string());
+                        }
+                    });
+                });
+
                 flex.add_flex(
                     item(),
                     Flex::horizontal().grow_items(1.0).w_full(),
@@ -272,22 +2089,14 @@ impl eframe::App for App {
                             .add(item().min_height(30.0), Button::new("-10"))
                             .clicked()
                         {
-                            self.value = CardNumber::try_new(
-                                self.value.get().saturating_sub(10),
-                                self.max_value.get(),
-                            )
-                            .unwrap_or(self.value);
+                            self.step_card(-10);
                         }
 
                         if flex
                             .add(item().min_height(30.0), Button::new("+10"))
                             .clicked()
                         {
-                            self.value = CardNumber::try_new(
-                                self.value.get().saturating_add(10),
-                                self.max_value.get(),
-                            )
-                            .unwrap_or(self.value);
+                            self.step_card(10);
                         }
                     },
                 );
@@ -300,64 +2109,181 @@ impl eframe::App for App {
                             .add(item().min_height(30.0), Button::new("-1"))
                             .clicked()
                         {
-                            self.value = CardNumber::try_new(
-                                self.value.get().saturating_sub(1),
-                                self.max_value.get(),
-                            )
-                            .unwrap_or(self.value);
+                            self.step_card(-1);
                         }
 
                         if flex
                             .add(item().min_height(30.0), Button::new("+1"))
                             .clicked()
                         {
-                            self.value = CardNumber::try_new(
-                                self.value.get().saturating_add(1),
-                                self.max_value.get(),
-                            )
-                            .unwrap_or(self.value);
+                            self.step_card(1);
                         }
                     },
                 );
 
                 if flex.add(item(), Button::new("Reset")).clicked() {
-                    self.value = CardNumber::try_new(1, self.max_value.get())
-                        .expect("Default value should be 1");
+                    self.reset_card();
                 }
             });
 
-            ui.separator();
+            // Picking a card by number brings its spread back into view
+            if (self.selected_set, self.value) != selected {
+                self.spread_cursor = None;
+            }
 
-            let slot = BinderSlot::from_index(&self.binder, self.value.to_index());
+            ui.separator();
 
             ui.vertical(|ui| {
                 ui.label("Card binder location: ");
 
-                ui.horizontal(|ui| {
-                    ui.label("Page: ");
-                    ui.label(format!("{}/{}", slot.page(), self.binder.pages()));
-                });
+                // Warn when the sets need more slots than the binders hold
+                let capacity = self
+                    .collection
+                    .capacity()
+                    .and_then(|capacity| Ok((self.sets.end(&self.collection)?, capacity)));
+                match capacity {
+                    Ok((card_count, capacity)) if capacity < card_count => {
+                        ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            format!(
+                                "The sets need {card_count} slots but the binders only hold {capacity}"
+                            ),
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        ui.colored_label(ui.visuals().warn_fg_color, e.to_string());
+                    }
+                }
 
-                ui.horizontal(|ui| {
-                    ui.label("Slot: "); // Slot on the current page
-                    ui.label(format!(
-                        "{}/{}",
-                        slot.index().get() % self.binder.total_page_slots() + 1,
-                        self.binder.total_page_slots()
-                    ));
-                });
+                match self
+                    .sets
+                    .locate(&self.collection, self.selected_set, self.value)
+                {
+                    Ok(location) => {
+                        let binder = self
+                            .collection
+                            .binder(location.binder())
+                            .expect("Located binder should exist");
+                        let slot = location.slot();
+
+                        let info = self
+                            .set()
+                            .card_info(self.value)
+                            .map(|info| format!(" {info}"))
+                            .unwrap_or_default();
+                        ui.label(format!(
+                            "#{}{info}: Binder {}, page {}, slot {}",
+                            self.set().label(self.value),
+                            location.binder(),
+                            slot.page(),
+                            slot.slot_on_page(binder)
+                        ));
+
+                        let card_count = self.set().card_count();
+                        let value = self.value;
+                        let ownership = self
+                            .sets
+                            .set_mut(self.selected_set)
+                            .expect("Selected set should exist")
+                            .ownership_mut();
+                        ui.horizontal(|ui| {
+                            ui.label("Copies owned:");
+
+                            let mut count = ownership.count(value);
+                            if ui
+                                .add(egui::DragValue::new(&mut count).range(0..=u16::MAX))
+                                .changed()
+                            {
+                                ownership.set_count(value, count);
+                            }
+
+                            ui.label(format!(
+                                "({} of {card_count} cards owned)",
+                                ownership.owned_count()
+                            ));
+                        });
+                    }
+                    Err(e) => {
+                        ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            format!("Card {} does not fit: {e}", self.set().label(self.value)),
+                        );
+                    }
+                }
             });
 
             ui.horizontal(|ui| {
-                card_table("visual_table_left", ui, self, CardTable::Left);
+                if ui.button("◀ Previous spread").clicked() {
+                    self.step_spread(false);
+                }
+                if ui.button("Next spread ▶").clicked() {
+                    self.step_spread(true);
+                }
+
+                let cursor = self.shown_spread();
+                let mut binder_number = cursor.binder;
+                if self.collection.len() > 1 {
+                    ui.label("Binder");
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut binder_number)
+                                .range(1..=self.collection.len()),
+                        )
+                        .changed()
+                    {
+                        self.spread_cursor = Some(SpreadCursor {
+                            binder: binder_number,
+                            spread: 0,
+                        });
+                    }
+                }
+
+                if let Some(binder) = self.collection.binder(cursor.binder) {
+                    // Jump to the spread showing the page
+                    let mut page = binder
+                        .spread_page(cursor.spread, PageSide::Left)
+                        .or_else(|| binder.spread_page(cursor.spread, PageSide::Right))
+                        .unwrap_or(1);
+                    ui.label("Page");
+                    if ui
+                        .add(egui::DragValue::new(&mut page).range(1..=binder.pages()))
+                        .changed()
+                    {
+                        self.spread_cursor = Some(SpreadCursor {
+                            binder: cursor.binder,
+                            spread: binder.spread_of(page),
+                        });
+                    }
+                }
+
+                if ui
+                    .add_enabled(
+                        self.selected_spread()
+                            .is_some_and(|selected| selected != cursor),
+                        Button::new("Go to selected card"),
+                    )
+                    .clicked()
+                {
+                    self.spread_cursor = None;
+                }
+            });
+
+            let cursor = self.shown_spread();
+            ui.horizontal(|ui| {
+                card_table("visual_table_left", ui, self, cursor, PageSide::Left);
                 ui.separator();
-                card_table("visual_table_right", ui, self, CardTable::Right);
+                card_table("visual_table_right", ui, self, cursor, PageSide::Right);
             });
 
+            if let Some(e) = &self.reserve_error {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("Cannot reserve pocket: {e}"));
+            }
+
             // Flex::horizontal().show(ui, |flex| {
             //     flex.add_flex(item(), Flex::vertical(), |flex| {
             //         flex.add_ui(item(), |ui| {
-            //             card_table("visual_table_left", ui, self, CardTable::Left);
+            //             card_table("visual_table_left", ui, self, PageSide::Left);
             //         });
             //
             //         flex.add(
@@ -372,7 +2298,7 @@ impl eframe::App for App {
             //
             //     flex.add_flex(item(), Flex::vertical(), |flex| {
             //         flex.add_ui(item(), |ui| {
-            //             card_table("visual_table_right", ui, self, CardTable::Right);
+            //             card_table("visual_table_right", ui, self, PageSide::Right);
             //         });
             //
             //         flex.add(
@@ -386,3 +2312,23 @@ impl eframe::App for App {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy() {
+        let mut app: App = serde_json::from_str(
+            r#"{"value":7,"max_value":165,"binder":{"rows":4,"cols":3,"pages":10}}"#,
+        )
+        .unwrap();
+        app.migrate_legacy();
+
+        assert_eq!(app.collection, Collection::new(Binder::new(4, 3, 10)));
+        assert_eq!(app.sets.len(), 1);
+        assert_eq!(app.set().card_count(), 165);
+        assert_eq!(app.value.get(), 7);
+        assert!(!serde_json::to_string(&app).unwrap().contains("max_value"));
+    }
+}
@@ -1,19 +1,176 @@
-#![allow(dead_code)]
-
-use std::num::NonZeroU32;
+use std::{collections::BTreeSet, num::NonZeroU32};
 
 use crate::card_number::{CardNumber, SlotIndex};
-use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A dimension of a binder or collection.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Dimension {
+    Rows,
+    Columns,
+    Pages,
+    Binders,
+    Sets,
+    Cards,
+}
+
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dimension::Rows => write!(f, "Rows"),
+            Dimension::Columns => write!(f, "Columns"),
+            Dimension::Pages => write!(f, "Pages"),
+            Dimension::Binders => write!(f, "Binders"),
+            Dimension::Sets => write!(f, "Sets"),
+            Dimension::Cards => write!(f, "Cards"),
+        }
+    }
+}
+
+/// Errors that can occur when building a binder or mapping slots within it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Error)]
+pub enum BinderError {
+    /// A binder dimension was zero.
+    #[error("{0} should be non-zero")]
+    ZeroDimension(Dimension),
+    /// The number of slots in the binder does not fit in a `u32`.
+    #[error("Binder capacity overflows a u32")]
+    Overflow,
+    /// The 0-based slot index lies past the last slot of the binder.
+    #[error("Position {} is beyond the capacity of {capacity} cards", .index + 1)]
+    BeyondCapacity { index: u32, capacity: u32 },
+    /// A page range ends before it starts.
+    #[error("Page range {first}..={last} is empty")]
+    EmptyPageRange { first: u32, last: u32 },
+    /// A pocket is reserved, so no card belongs in it.
+    #[error("Page {page}, row {row}, column {col} is reserved")]
+    Reserved { page: u32, row: u32, col: u32 },
+    /// A 1-based position lies outside the binder.
+    #[error("{dimension} position {position} is outside the range 1..={max}")]
+    OutOfBounds {
+        dimension: Dimension,
+        position: u32,
+        max: u32,
+    },
+}
+
+/// A side of an open binder.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum PageSide {
+    Left,
+    #[default]
+    Right,
+}
+
+/// Whether both sides of each sheet in a binder hold cards.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum Sidedness {
+    /// Both the front and back of each sheet are pages.
+    #[default]
+    DoubleSided,
+    /// Only the front of each sheet is a page, so every spread shows a single page.
+    SingleSided,
+}
+
+/// The order in which the slots on a page are filled.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum FillOrder {
+    /// Fill each row left to right, top to bottom.
+    #[default]
+    RowMajor,
+    /// Fill each row right to left, top to bottom.
+    RightToLeft,
+    /// Fill each column top to bottom, left to right.
+    ColumnMajor,
+    /// Fill rows top to bottom, alternating left to right and right to left.
+    Serpentine,
+}
 
+/// A range of pages with their own pocket layout.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub struct PageLayout {
+    /// The first page the layout applies to.
+    first_page: NonZeroU32,
+    /// The last page the layout applies to.
+    last_page: NonZeroU32,
+    /// The number of rows on each page in the range.
+    rows: NonZeroU32,
+    /// The number of columns on each page in the range.
+    cols: NonZeroU32,
+}
+
+/// A 1-based page, row and column of a pocket in a binder.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Deserialize, Serialize)]
+pub struct Pocket {
+    page: u32,
+    row: u32,
+    col: u32,
+}
+
+impl Pocket {
+    /// Create a new pocket position from a 1-based page, row and column.
+    pub fn new(page: u32, row: u32, col: u32) -> Self {
+        Self { page, row, col }
+    }
+
+    /// Get the page number of the pocket.
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Get the row number of the pocket.
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    /// Get the column number of the pocket.
+    pub fn col(&self) -> u32 {
+        self.col
+    }
+}
+
+/// Where the first card of a set is placed relative to the cards before it.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum Alignment {
+    /// Start in the pocket after the previous card.
+    #[default]
+    Continue,
+    /// Start on the first pocket of a new page.
+    NewPage,
+    /// Start on the first pocket of a new spread.
+    NewSpread,
+    /// Start in the first pocket of a new binder.
+    NewBinder,
+}
+
+/// A struct to represent the layout of a card binder.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct Binder {
-    /// The number of rows on each page.
+    /// The number of rows on each page without its own layout.
     rows: NonZeroU32,
-    /// The number of columns on each page.
+    /// The number of columns on each page without its own layout.
     cols: NonZeroU32,
     /// The number of pages in the binder.
     pages: NonZeroU32,
+    /// The side of the first spread that page 1 is on.
+    #[serde(default)]
+    first_page_side: PageSide,
+    /// Whether both sides of each sheet hold cards.
+    #[serde(default)]
+    sidedness: Sidedness,
+    /// The order in which the slots on a page are filled.
+    #[serde(default)]
+    fill_order: FillOrder,
+    /// Whether pages on the left of a spread are filled as a mirror image of the fill order.
+    #[serde(default)]
+    mirror_left_pages: bool,
+    /// Page ranges with their own pocket layout. Later layouts take precedence.
+    #[serde(default)]
+    page_layouts: Vec<PageLayout>,
+    /// Pockets that are skipped when numbering cards.
+    #[serde(default)]
+    reserved: BTreeSet<Pocket>,
 }
 
 /// A struct to represent a card slot in the card binder.
@@ -29,6 +186,85 @@ pub struct BinderSlot {
     index: SlotIndex,
 }
 
+fn non_zero(value: u32, dimension: Dimension) -> Result<NonZeroU32, BinderError> {
+    NonZeroU32::new(value).ok_or(BinderError::ZeroDimension(dimension))
+}
+
+impl PageLayout {
+    /// Create a new layout of `rows` by `cols` for the pages `first_page..=last_page`.
+    ///
+    /// Fails if the number of pockets on a page does not fit in a `u32`.
+    pub fn new(first_page: u32, last_page: u32, rows: u32, cols: u32) -> Result<Self, BinderError> {
+        let rows = non_zero(rows, Dimension::Rows)?;
+        let cols = non_zero(cols, Dimension::Columns)?;
+        rows.checked_mul(cols).ok_or(BinderError::Overflow)?;
+
+        let mut layout = Self {
+            first_page: NonZeroU32::MIN,
+            last_page: NonZeroU32::MIN,
+            rows,
+            cols,
+        };
+        layout.set_page_range(first_page, last_page)?;
+        Ok(layout)
+    }
+
+    /// Get the first page the layout applies to.
+    pub fn first_page(&self) -> u32 {
+        self.first_page.get()
+    }
+
+    /// Get the last page the layout applies to.
+    pub fn last_page(&self) -> u32 {
+        self.last_page.get()
+    }
+
+    /// Get the number of rows on each page in the range.
+    pub fn rows(&self) -> u32 {
+        self.rows.get()
+    }
+
+    /// Get the number of columns on each page in the range.
+    pub fn cols(&self) -> u32 {
+        self.cols.get()
+    }
+
+    /// Check if the layout applies to a 1-based page.
+    pub fn contains(&self, page: u32) -> bool {
+        (self.first_page()..=self.last_page()).contains(&page)
+    }
+
+    /// Update the range of pages the layout applies to.
+    pub fn set_page_range(&mut self, first_page: u32, last_page: u32) -> Result<(), BinderError> {
+        if first_page > last_page {
+            return Err(BinderError::EmptyPageRange {
+                first: first_page,
+                last: last_page,
+            });
+        }
+
+        self.first_page = non_zero(first_page, Dimension::Pages)?;
+        self.last_page = non_zero(last_page, Dimension::Pages)?;
+        Ok(())
+    }
+
+    /// Update the number of rows on each page in the range.
+    pub fn set_rows(&mut self, rows: u32) -> Result<(), BinderError> {
+        let rows = non_zero(rows, Dimension::Rows)?;
+        rows.checked_mul(self.cols).ok_or(BinderError::Overflow)?;
+        self.rows = rows;
+        Ok(())
+    }
+
+    /// Update the number of columns on each page in the range.
+    pub fn set_cols(&mut self, cols: u32) -> Result<(), BinderError> {
+        let cols = non_zero(cols, Dimension::Columns)?;
+        self.rows.checked_mul(cols).ok_or(BinderError::Overflow)?;
+        self.cols = cols;
+        Ok(())
+    }
+}
+
 impl Binder {
     /// Create a new binder with the given number of rows and columns.
     pub fn new(rows: u32, cols: u32, pages: u32) -> Self {
@@ -36,70 +272,533 @@ impl Binder {
             rows: NonZeroU32::new(rows).expect("Rows should be non-zero"),
             cols: NonZeroU32::new(cols).expect("Columns should be non-zero"),
             pages: NonZeroU32::new(pages).expect("Pages should be non-zero"),
+            first_page_side: PageSide::default(),
+            sidedness: Sidedness::default(),
+            fill_order: FillOrder::default(),
+            mirror_left_pages: false,
+            page_layouts: Vec::new(),
+            reserved: BTreeSet::new(),
         }
     }
 
+    /// Try to create a new binder, failing if any dimension is zero.
+    pub fn try_new(rows: u32, cols: u32, pages: u32) -> Result<Self, BinderError> {
+        Ok(Self {
+            rows: non_zero(rows, Dimension::Rows)?,
+            cols: non_zero(cols, Dimension::Columns)?,
+            pages: non_zero(pages, Dimension::Pages)?,
+            first_page_side: PageSide::default(),
+            sidedness: Sidedness::default(),
+            fill_order: FillOrder::default(),
+            mirror_left_pages: false,
+            page_layouts: Vec::new(),
+            reserved: BTreeSet::new(),
+        })
+    }
+
     /// Get the number of pages in the binder.
     pub fn pages(&self) -> u32 {
         self.pages.get()
     }
 
-    /// Get the number of rows on a page.
+    /// Get the number of rows on pages without their own layout.
     pub fn rows(&self) -> u32 {
         self.rows.get()
     }
 
-    /// Get the number of columns on a page.
+    /// Get the number of columns on pages without their own layout.
     pub fn cols(&self) -> u32 {
         self.cols.get()
     }
 
+    /// Get the layout that applies to a 1-based page, if it has its own.
+    fn layout_of(&self, page: u32) -> Option<&PageLayout> {
+        self.page_layouts
+            .iter()
+            .rev()
+            .find(|layout| layout.contains(page))
+    }
+
+    /// Get the number of rows on a 1-based page.
+    pub fn rows_on(&self, page: u32) -> u32 {
+        self.layout_of(page).map_or(self.rows(), PageLayout::rows)
+    }
+
+    /// Get the number of columns on a 1-based page.
+    pub fn cols_on(&self, page: u32) -> u32 {
+        self.layout_of(page).map_or(self.cols(), PageLayout::cols)
+    }
+
+    /// Get the number of slots on a 1-based page.
+    pub fn page_slots(&self, page: u32) -> u32 {
+        self.rows_on(page).saturating_mul(self.cols_on(page))
+    }
+
+    /// Get the page ranges with their own pocket layout.
+    pub fn page_layouts(&self) -> &[PageLayout] {
+        &self.page_layouts
+    }
+
+    /// Get the page ranges with their own pocket layout for editing.
+    pub fn page_layouts_mut(&mut self) -> &mut [PageLayout] {
+        &mut self.page_layouts
+    }
+
+    /// Add a page range with its own pocket layout, taking precedence over earlier layouts.
+    pub fn add_page_layout(&mut self, layout: PageLayout) {
+        self.page_layouts.push(layout);
+    }
+
+    /// Remove a page layout by its 0-based position in [`Binder::page_layouts`].
+    pub fn remove_page_layout(&mut self, index: usize) -> Option<PageLayout> {
+        (index < self.page_layouts.len()).then(|| self.page_layouts.remove(index))
+    }
+
+    /// Get the 0-based index of the first slot on a 1-based page.
+    ///
+    /// Pages past the end of the binder are assumed to use the default layout.
+    pub fn page_start(&self, page: u32) -> Result<u32, BinderError> {
+        let before = page.saturating_sub(1);
+        if self.page_layouts.is_empty() {
+            return before
+                .checked_mul(self.total_page_slots())
+                .ok_or(BinderError::Overflow);
+        }
+
+        let within = before.min(self.pages());
+        let start = (1..=within).try_fold(0u32, |total, page| {
+            total
+                .checked_add(self.page_slots(page))
+                .ok_or(BinderError::Overflow)
+        })?;
+
+        (before - within)
+            .checked_mul(self.total_page_slots())
+            .and_then(|beyond| start.checked_add(beyond))
+            .ok_or(BinderError::Overflow)
+    }
+
+    /// For a 0-based index, return the 1-based page and the 0-based offset on that page.
+    ///
+    /// Pages past the end of the binder are assumed to use the default layout.
+    fn page_of(&self, index: u32) -> (u32, u32) {
+        let mut remaining = index;
+        if !self.page_layouts.is_empty() {
+            for page in 1..=self.pages() {
+                let slots = self.page_slots(page);
+                if remaining < slots {
+                    return (page, remaining);
+                }
+
+                remaining -= slots;
+            }
+        }
+
+        let skipped = if self.page_layouts.is_empty() {
+            0
+        } else {
+            self.pages()
+        };
+        let slots = self.total_page_slots();
+        (
+            skipped.saturating_add(remaining / slots).saturating_add(1),
+            remaining % slots,
+        )
+    }
+
     /// Update the number of pages in the binder.
-    pub fn set_pages(&mut self, pages: u32) -> Result<()> {
-        self.pages = NonZeroU32::new(pages).context("Pages should be non-zero")?;
+    pub fn set_pages(&mut self, pages: u32) -> Result<(), BinderError> {
+        self.pages = non_zero(pages, Dimension::Pages)?;
         Ok(())
     }
 
     /// Update the number of rows on a page.
-    pub fn set_rows(&mut self, rows: u32) -> Result<()> {
-        self.rows = NonZeroU32::new(rows).context("Rows should be non-zero")?;
+    pub fn set_rows(&mut self, rows: u32) -> Result<(), BinderError> {
+        self.rows = non_zero(rows, Dimension::Rows)?;
         Ok(())
     }
 
     /// Update the number of columns on a page.
-    pub fn set_cols(&mut self, cols: u32) -> Result<()> {
-        self.cols = NonZeroU32::new(cols).context("Columns should be non-zero")?;
+    pub fn set_cols(&mut self, cols: u32) -> Result<(), BinderError> {
+        self.cols = non_zero(cols, Dimension::Columns)?;
         Ok(())
     }
 
-    /// Get the number of slots on a page.
+    /// Get the number of slots on pages without their own layout.
     pub fn total_page_slots(&self) -> u32 {
         self.rows.saturating_mul(self.cols).get()
     }
+
+    /// Get the number of cards the binder holds, excluding reserved pockets.
+    pub fn capacity(&self) -> Result<u32, BinderError> {
+        Ok(self.pockets()? - self.reserved_indices().len() as u32)
+    }
+
+    /// Get the number of pockets in the whole binder, including reserved ones.
+    pub fn pockets(&self) -> Result<u32, BinderError> {
+        if self.page_layouts.is_empty() {
+            return self
+                .rows
+                .checked_mul(self.cols)
+                .and_then(|slots| slots.checked_mul(self.pages))
+                .map(NonZeroU32::get)
+                .ok_or(BinderError::Overflow);
+        }
+
+        (1..=self.pages()).try_fold(0u32, |total, page| {
+            self.rows_on(page)
+                .checked_mul(self.cols_on(page))
+                .and_then(|slots| total.checked_add(slots))
+                .ok_or(BinderError::Overflow)
+        })
+    }
+
+    /// Get the reserved pockets, including any that no longer fit the binder.
+    pub fn reserved(&self) -> &BTreeSet<Pocket> {
+        &self.reserved
+    }
+
+    /// Check if a pocket is reserved.
+    pub fn is_reserved(&self, pocket: Pocket) -> bool {
+        self.reserved.contains(&pocket)
+    }
+
+    /// Reserve a pocket so that card numbering skips it.
+    pub fn reserve(&mut self, pocket: Pocket) -> Result<(), BinderError> {
+        BinderSlot::from_position(self, pocket.page(), pocket.row(), pocket.col())?;
+        self.reserved.insert(pocket);
+        Ok(())
+    }
+
+    /// Release a reserved pocket, returning whether it was reserved.
+    pub fn unreserve(&mut self, pocket: Pocket) -> bool {
+        self.reserved.remove(&pocket)
+    }
+
+    /// Get the sorted slot indices of the reserved pockets that fit the binder.
+    fn reserved_indices(&self) -> Vec<u32> {
+        let mut indices: Vec<u32> = self
+            .reserved
+            .iter()
+            .filter_map(|pocket| {
+                BinderSlot::from_position(self, pocket.page(), pocket.row(), pocket.col()).ok()
+            })
+            .map(|slot| slot.index().get())
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Get the slot index that a card number is placed in, skipping reserved pockets.
+    pub fn slot_index_of(&self, card_number: CardNumber) -> SlotIndex {
+        let mut index = card_number.to_index().get();
+        for reserved in self.reserved_indices() {
+            if reserved > index {
+                break;
+            }
+
+            index = index.saturating_add(1);
+        }
+
+        SlotIndex::new(index)
+    }
+
+    /// Get the card number placed in a slot index, or `None` if the pocket is reserved.
+    pub fn card_number_at(&self, index: SlotIndex) -> Option<CardNumber> {
+        let reserved = self.reserved_indices();
+        if reserved.binary_search(&index.get()).is_ok() {
+            return None;
+        }
+
+        Some(SlotIndex::new(self.cards_before(index)).to_card_number())
+    }
+
+    /// Get the number of cards placed before a slot index, skipping reserved pockets.
+    pub fn cards_before(&self, index: SlotIndex) -> u32 {
+        let reserved = self.reserved_indices();
+        index.get() - reserved.partition_point(|&reserved| reserved < index.get()) as u32
+    }
+
+    /// Get the 0-based card index to start a run of cards at, given the
+    /// number of cards already placed in the binder.
+    ///
+    /// The result is at most [`Binder::capacity`], meaning the run starts in the next binder.
+    pub fn align(&self, card_index: u32, alignment: Alignment) -> Result<u32, BinderError> {
+        let capacity = self.capacity()?;
+        if card_index == 0 || card_index >= capacity {
+            return Ok(card_index);
+        }
+
+        let next = BinderSlot::from_card_number(self, SlotIndex::new(card_index).to_card_number());
+        let (current, following) = match alignment {
+            Alignment::Continue => return Ok(card_index),
+            Alignment::NewBinder => return Ok(capacity),
+            Alignment::NewPage => (next.page(), next.page().saturating_add(1)),
+            Alignment::NewSpread => {
+                let spread = self.spread_of(next.page());
+                (
+                    self.first_page_of_spread(spread),
+                    self.first_page_of_spread(spread.saturating_add(1)),
+                )
+            }
+        };
+
+        // Already at the start of a page or spread
+        let start = self.cards_before(SlotIndex::new(self.page_start(current)?));
+        if start == card_index {
+            return Ok(card_index);
+        }
+
+        let start = self.cards_before(SlotIndex::new(self.page_start(following)?));
+        Ok(start.min(capacity))
+    }
+
+    /// Get the side of the first spread that page 1 is on.
+    pub fn first_page_side(&self) -> PageSide {
+        self.first_page_side
+    }
+
+    /// Update the side of the first spread that page 1 is on.
+    pub fn set_first_page_side(&mut self, side: PageSide) {
+        self.first_page_side = side;
+    }
+
+    /// Get whether both sides of each sheet hold cards.
+    pub fn sidedness(&self) -> Sidedness {
+        self.sidedness
+    }
+
+    /// Update whether both sides of each sheet hold cards.
+    pub fn set_sidedness(&mut self, sidedness: Sidedness) {
+        self.sidedness = sidedness;
+    }
+
+    /// Get the order in which the slots on a page are filled.
+    pub fn fill_order(&self) -> FillOrder {
+        self.fill_order
+    }
+
+    /// Update the order in which the slots on a page are filled.
+    pub fn set_fill_order(&mut self, fill_order: FillOrder) {
+        self.fill_order = fill_order;
+    }
+
+    /// Get whether pages on the left of a spread are filled as a mirror image.
+    pub fn mirror_left_pages(&self) -> bool {
+        self.mirror_left_pages
+    }
+
+    /// Update whether pages on the left of a spread are filled as a mirror image.
+    pub fn set_mirror_left_pages(&mut self, mirror: bool) {
+        self.mirror_left_pages = mirror;
+    }
+
+    /// Check if the columns of a 1-based page are mirrored.
+    fn is_mirrored(&self, page: u32) -> bool {
+        self.mirror_left_pages && self.side_of(page) == PageSide::Left
+    }
+
+    /// For a 0-based offset into the fill order of a 1-based page, return the 0-based row and column.
+    fn cell_of(&self, page: u32, offset: u32) -> (u32, u32) {
+        let rows = self.rows_on(page);
+        let cols = self.cols_on(page);
+
+        let (row, col) = match self.fill_order {
+            FillOrder::RowMajor => (offset / cols, offset % cols),
+            FillOrder::RightToLeft => (offset / cols, cols - 1 - offset % cols),
+            FillOrder::ColumnMajor => (offset % rows, offset / rows),
+            FillOrder::Serpentine => {
+                let row = offset / cols;
+                if row.is_multiple_of(2) {
+                    (row, offset % cols)
+                } else {
+                    (row, cols - 1 - offset % cols)
+                }
+            }
+        };
+
+        if self.is_mirrored(page) {
+            (row, cols - 1 - col)
+        } else {
+            (row, col)
+        }
+    }
+
+    /// For a 0-based row and column on a 1-based page, return the 0-based offset into the fill order.
+    fn offset_of(&self, page: u32, row: u32, col: u32) -> u32 {
+        let rows = self.rows_on(page);
+        let cols = self.cols_on(page);
+        let col = if self.is_mirrored(page) {
+            cols - 1 - col
+        } else {
+            col
+        };
+
+        match self.fill_order {
+            FillOrder::RowMajor => row * cols + col,
+            FillOrder::RightToLeft => row * cols + (cols - 1 - col),
+            FillOrder::ColumnMajor => col * rows + row,
+            FillOrder::Serpentine => {
+                if row.is_multiple_of(2) {
+                    row * cols + col
+                } else {
+                    row * cols + (cols - 1 - col)
+                }
+            }
+        }
+    }
+
+    /// The number of empty page positions before page 1 when counting
+    /// across double-sided spreads.
+    fn spread_offset(&self) -> u32 {
+        match self.first_page_side {
+            PageSide::Left => 0,
+            PageSide::Right => 1,
+        }
+    }
+
+    /// Get the 0-based spread that a 1-based page is shown on.
+    pub fn spread_of(&self, page: u32) -> u32 {
+        match self.sidedness {
+            Sidedness::DoubleSided => (page.saturating_sub(1) + self.spread_offset()) / 2,
+            Sidedness::SingleSided => page.saturating_sub(1),
+        }
+    }
+
+    /// Get the side of its spread that a 1-based page is shown on.
+    pub fn side_of(&self, page: u32) -> PageSide {
+        match self.sidedness {
+            Sidedness::DoubleSided => {
+                if (page.saturating_sub(1) + self.spread_offset()).is_multiple_of(2) {
+                    PageSide::Left
+                } else {
+                    PageSide::Right
+                }
+            }
+            Sidedness::SingleSided => self.first_page_side,
+        }
+    }
+
+    /// Get the number of spreads needed to show every page.
+    pub fn spreads(&self) -> u32 {
+        self.spread_of(self.pages()) + 1
+    }
+
+    /// Get the first 1-based page shown on a 0-based spread, even if it is past the last page.
+    pub fn first_page_of_spread(&self, spread: u32) -> u32 {
+        match self.sidedness {
+            Sidedness::DoubleSided => spread
+                .saturating_mul(2)
+                .saturating_sub(self.spread_offset())
+                .saturating_add(1),
+            Sidedness::SingleSided => spread.saturating_add(1),
+        }
+    }
+
+    /// Get the 1-based page shown on one side of a 0-based spread, if any.
+    ///
+    /// Sides without a page, such as the unused back of a single-sided sheet, return `None`.
+    pub fn spread_page(&self, spread: u32, side: PageSide) -> Option<u32> {
+        let page = match self.sidedness {
+            Sidedness::DoubleSided => {
+                let position = spread.checked_mul(2)?.checked_add(match side {
+                    PageSide::Left => 0,
+                    PageSide::Right => 1,
+                })?;
+                position.checked_sub(self.spread_offset())? + 1
+            }
+            Sidedness::SingleSided => {
+                if side != self.first_page_side {
+                    return None;
+                }
+                spread.checked_add(1)?
+            }
+        };
+
+        (page <= self.pages()).then_some(page)
+    }
+}
+
+/// Check that a 1-based position lies within `1..=max`.
+fn check_bounds(dimension: Dimension, position: u32, max: u32) -> Result<(), BinderError> {
+    if (1..=max).contains(&position) {
+        Ok(())
+    } else {
+        Err(BinderError::OutOfBounds {
+            dimension,
+            position,
+            max,
+        })
+    }
 }
 
 impl BinderSlot {
     /// For a given index, return the corresponding card slot.
+    ///
+    /// The page is not checked against the binder; see [`BinderSlot::try_from_index`].
     pub fn from_index(binder: &Binder, index: SlotIndex) -> Self {
-        let rows = binder.rows();
-        let cols = binder.cols();
-
-        let page = index.get() / (rows * cols);
-        let row = (index.get() % (rows * cols)) / cols;
-        let col = index.get() % cols;
+        let (page, offset) = binder.page_of(index.get());
+        let (row, col) = binder.cell_of(page, offset);
 
         Self {
-            page: NonZeroU32::new(page + 1).expect("Page number should be non-zero"),
-            row: NonZeroU32::new(row + 1).expect("Row number should be non-zero"),
-            col: NonZeroU32::new(col + 1).expect("Column number should be non-zero"),
+            page: NonZeroU32::new(page).expect("Page number should be non-zero"),
+            row: NonZeroU32::MIN.saturating_add(row),
+            col: NonZeroU32::MIN.saturating_add(col),
             index,
         }
     }
 
-    /// For a given card number, return the corresponding card slot.
+    /// For a given index, return the corresponding card slot if it lies within the binder.
+    pub fn try_from_index(binder: &Binder, index: SlotIndex) -> Result<Self, BinderError> {
+        let capacity = binder.pockets()?;
+        if index.get() >= capacity {
+            return Err(BinderError::BeyondCapacity {
+                index: index.get(),
+                capacity,
+            });
+        }
+
+        Ok(Self::from_index(binder, index))
+    }
+
+    /// For a given card number, return the corresponding card slot, skipping reserved pockets.
     pub fn from_card_number(binder: &Binder, card_number: CardNumber) -> Self {
-        let index = card_number.to_index();
-        Self::from_index(binder, index)
+        Self::from_index(binder, binder.slot_index_of(card_number))
+    }
+
+    /// For a given card number, return the corresponding card slot if it lies within the binder.
+    pub fn try_from_card_number(
+        binder: &Binder,
+        card_number: CardNumber,
+    ) -> Result<Self, BinderError> {
+        let capacity = binder.capacity()?;
+        if card_number.to_index().get() >= capacity {
+            return Err(BinderError::BeyondCapacity {
+                index: card_number.to_index().get(),
+                capacity,
+            });
+        }
+
+        Ok(Self::from_card_number(binder, card_number))
+    }
+
+    /// For a given 1-based page, row and column, return the corresponding card slot.
+    pub fn from_position(
+        binder: &Binder,
+        page: u32,
+        row: u32,
+        col: u32,
+    ) -> Result<Self, BinderError> {
+        check_bounds(Dimension::Pages, page, binder.pages())?;
+        check_bounds(Dimension::Rows, row, binder.rows_on(page))?;
+        check_bounds(Dimension::Columns, col, binder.cols_on(page))?;
+
+        let index = binder
+            .page_start(page)?
+            .checked_add(binder.offset_of(page, row - 1, col - 1))
+            .ok_or(BinderError::Overflow)?;
+
+        Ok(Self::from_index(binder, SlotIndex::new(index)))
     }
 
     /// Get the page number of the card slot.
@@ -117,10 +816,26 @@ impl BinderSlot {
         self.col.get()
     }
 
+    /// Get the 1-based position of the card slot on its page.
+    pub fn slot_on_page(&self, binder: &Binder) -> u32 {
+        let start = binder.page_start(self.page()).unwrap_or_default();
+        self.index.get().saturating_sub(start) + 1
+    }
+
     /// Convert a binder slot to an index.
     pub fn index(&self) -> SlotIndex {
         self.index
     }
+
+    /// Get the position of the card slot.
+    pub fn pocket(&self) -> Pocket {
+        Pocket::new(self.page(), self.row(), self.col())
+    }
+
+    /// Get the card number that belongs in the card slot, or `None` if it is reserved.
+    pub fn card_number(&self, binder: &Binder) -> Option<CardNumber> {
+        binder.card_number_at(self.index)
+    }
 }
 
 #[cfg(test)]
@@ -132,12 +847,238 @@ mod tests {
         let binder = Binder::new(3, 3, 20);
         let slot = BinderSlot::from_index(&binder, SlotIndex::new(10));
         let index = slot.index();
-        assert_eq!(index.get(), 11);
+        assert_eq!(index.get(), 10);
+        assert_eq!(slot.card_number(&binder).unwrap().get(), 11);
+    }
+
+    #[test]
+    fn test_from_position() {
+        let binder = Binder::new(3, 3, 20);
+        let slot = BinderSlot::from_position(&binder, 2, 1, 2).unwrap();
+        assert_eq!(slot.card_number(&binder).unwrap().get(), 11);
+        assert_eq!(slot, BinderSlot::from_index(&binder, slot.index()));
+
+        assert!(BinderSlot::from_position(&binder, 0, 1, 1).is_err());
+        assert!(BinderSlot::from_position(&binder, 21, 1, 1).is_err());
+        assert!(BinderSlot::from_position(&binder, 1, 4, 1).is_err());
+        assert!(BinderSlot::from_position(&binder, 1, 1, 4).is_err());
+    }
+
+    #[test]
+    fn test_slot_on_page() {
+        let binder = Binder::new(3, 3, 20);
+        let slot = BinderSlot::from_index(&binder, SlotIndex::new(10));
+        assert_eq!(slot.slot_on_page(&binder), 2);
     }
 
     #[test]
     fn test_binder() {
         let binder = Binder::new(3, 3, 20);
         assert_eq!(binder.total_page_slots(), 9);
+        assert_eq!(binder.capacity(), Ok(180));
+    }
+
+    #[test]
+    fn test_spreads() {
+        let mut binder = Binder::new(3, 3, 4);
+        assert_eq!(binder.spread_page(0, PageSide::Left), None);
+        assert_eq!(binder.spread_page(0, PageSide::Right), Some(1));
+        assert_eq!(binder.spread_page(1, PageSide::Left), Some(2));
+        assert_eq!(binder.spread_page(2, PageSide::Left), Some(4));
+        assert_eq!(binder.spread_page(2, PageSide::Right), None);
+        assert_eq!(binder.spread_of(3), 1);
+        assert_eq!(binder.side_of(3), PageSide::Right);
+        assert_eq!(binder.spreads(), 3);
+
+        binder.set_first_page_side(PageSide::Left);
+        assert_eq!(binder.spread_page(0, PageSide::Left), Some(1));
+        assert_eq!(binder.spread_page(0, PageSide::Right), Some(2));
+        assert_eq!(binder.spread_of(3), 1);
+        assert_eq!(binder.side_of(3), PageSide::Left);
+        assert_eq!(binder.spreads(), 2);
+
+        binder.set_sidedness(Sidedness::SingleSided);
+        assert_eq!(binder.spread_page(2, PageSide::Left), Some(3));
+        assert_eq!(binder.spread_page(2, PageSide::Right), None);
+        assert_eq!(binder.spread_of(3), 2);
+        assert_eq!(binder.side_of(3), PageSide::Left);
+        assert_eq!(binder.spreads(), 4);
+    }
+
+    #[test]
+    fn test_fill_orders() {
+        let mut binder = Binder::new(2, 3, 4);
+        let cell = |binder: &Binder, index| {
+            let slot = BinderSlot::from_index(binder, SlotIndex::new(index));
+            (slot.page(), slot.row(), slot.col())
+        };
+
+        assert_eq!(cell(&binder, 4), (1, 2, 2));
+
+        binder.set_fill_order(FillOrder::RightToLeft);
+        assert_eq!(cell(&binder, 0), (1, 1, 3));
+        assert_eq!(cell(&binder, 4), (1, 2, 2));
+
+        binder.set_fill_order(FillOrder::ColumnMajor);
+        assert_eq!(cell(&binder, 1), (1, 2, 1));
+        assert_eq!(cell(&binder, 4), (1, 1, 3));
+
+        binder.set_fill_order(FillOrder::Serpentine);
+        assert_eq!(cell(&binder, 2), (1, 1, 3));
+        assert_eq!(cell(&binder, 3), (1, 2, 3));
+
+        // Page 2 is on the left of the second spread
+        binder.set_fill_order(FillOrder::RowMajor);
+        binder.set_mirror_left_pages(true);
+        assert_eq!(cell(&binder, 0), (1, 1, 1));
+        assert_eq!(cell(&binder, 6), (2, 1, 3));
+
+        for order in [
+            FillOrder::RowMajor,
+            FillOrder::RightToLeft,
+            FillOrder::ColumnMajor,
+            FillOrder::Serpentine,
+        ] {
+            binder.set_fill_order(order);
+            for index in 0..binder.pockets().unwrap() {
+                let slot = BinderSlot::from_index(&binder, SlotIndex::new(index));
+                let round_trip =
+                    BinderSlot::from_position(&binder, slot.page(), slot.row(), slot.col());
+                assert_eq!(round_trip, Ok(slot));
+            }
+        }
+    }
+
+    #[test]
+    fn test_page_layouts() {
+        let mut binder = Binder::new(3, 3, 10);
+        binder.add_page_layout(PageLayout::new(2, 3, 2, 2).unwrap());
+        binder.add_page_layout(PageLayout::new(10, 10, 3, 4).unwrap());
+
+        assert_eq!(binder.page_slots(1), 9);
+        assert_eq!(binder.page_slots(2), 4);
+        assert_eq!(binder.page_slots(10), 12);
+        assert_eq!(binder.capacity(), Ok(9 + 4 + 4 + 9 * 6 + 12));
+        assert_eq!(binder.page_start(4), Ok(17));
+
+        let slot = BinderSlot::from_index(&binder, SlotIndex::new(12));
+        assert_eq!((slot.page(), slot.row(), slot.col()), (2, 2, 2));
+        assert_eq!(slot.slot_on_page(&binder), 4);
+
+        let slot = BinderSlot::from_index(&binder, SlotIndex::new(13));
+        assert_eq!((slot.page(), slot.row(), slot.col()), (3, 1, 1));
+
+        let slot = BinderSlot::from_index(&binder, SlotIndex::new(71 + 11));
+        assert_eq!((slot.page(), slot.row(), slot.col()), (10, 3, 4));
+
+        assert!(BinderSlot::from_position(&binder, 2, 3, 1).is_err());
+        for index in 0..binder.pockets().unwrap() {
+            let slot = BinderSlot::from_index(&binder, SlotIndex::new(index));
+            let round_trip =
+                BinderSlot::from_position(&binder, slot.page(), slot.row(), slot.col());
+            assert_eq!(round_trip, Ok(slot));
+        }
+
+        assert_eq!(
+            PageLayout::new(3, 2, 2, 2),
+            Err(BinderError::EmptyPageRange { first: 3, last: 2 })
+        );
+
+        // Every pocket on a page needs an offset that fits in a u32
+        assert_eq!(
+            PageLayout::new(1, 1, 1 << 16, 1 << 16),
+            Err(BinderError::Overflow)
+        );
+        let mut layout = PageLayout::new(1, 1, 1 << 16, 1).unwrap();
+        assert_eq!(layout.set_cols(1 << 16), Err(BinderError::Overflow));
+        assert_eq!(layout.cols(), 1);
+        let mut layout = PageLayout::new(1, 1, 1, 1 << 16).unwrap();
+        assert_eq!(layout.set_rows(1 << 16), Err(BinderError::Overflow));
+        assert_eq!(layout.rows(), 1);
+    }
+
+    #[test]
+    fn test_reserved() {
+        let mut binder = Binder::new(3, 3, 2);
+        binder.reserve(Pocket::new(1, 1, 1)).unwrap();
+        binder.reserve(Pocket::new(1, 2, 2)).unwrap();
+        assert!(binder.reserve(Pocket::new(3, 1, 1)).is_err());
+        assert_eq!(binder.capacity(), Ok(16));
+        assert_eq!(binder.pockets(), Ok(18));
+
+        let slot = |binder: &Binder, n| {
+            BinderSlot::from_card_number(binder, CardNumber::try_new(n, 16).unwrap())
+        };
+        assert_eq!(slot(&binder, 1).pocket(), Pocket::new(1, 1, 2));
+        assert_eq!(slot(&binder, 3).pocket(), Pocket::new(1, 2, 1));
+        assert_eq!(slot(&binder, 4).pocket(), Pocket::new(1, 2, 3));
+
+        let reserved = BinderSlot::from_position(&binder, 1, 2, 2).unwrap();
+        assert_eq!(reserved.card_number(&binder), None);
+        for n in 1..=16 {
+            assert_eq!(slot(&binder, n).card_number(&binder).unwrap().get(), n);
+        }
+
+        assert!(
+            BinderSlot::try_from_card_number(&binder, CardNumber::try_new(17, 17).unwrap())
+                .is_err()
+        );
+
+        assert!(binder.unreserve(Pocket::new(1, 1, 1)));
+        assert_eq!(slot(&binder, 1).pocket(), Pocket::new(1, 1, 1));
+    }
+
+    #[test]
+    fn test_align() {
+        let mut binder = Binder::new(3, 3, 10);
+        assert_eq!(binder.align(0, Alignment::NewPage), Ok(0));
+        assert_eq!(binder.align(5, Alignment::Continue), Ok(5));
+        assert_eq!(binder.align(5, Alignment::NewPage), Ok(9));
+        assert_eq!(binder.align(9, Alignment::NewPage), Ok(9));
+        assert_eq!(binder.align(5, Alignment::NewBinder), Ok(90));
+
+        // Page 1 is alone on the first spread, then pages 2 and 3
+        assert_eq!(binder.align(5, Alignment::NewSpread), Ok(9));
+        assert_eq!(binder.align(10, Alignment::NewSpread), Ok(27));
+        assert_eq!(binder.align(27, Alignment::NewSpread), Ok(27));
+        assert_eq!(binder.align(85, Alignment::NewSpread), Ok(90));
+
+        binder.reserve(Pocket::new(2, 1, 1)).unwrap();
+        assert_eq!(binder.align(5, Alignment::NewPage), Ok(9));
+        assert_eq!(binder.align(9, Alignment::NewPage), Ok(9));
+        assert_eq!(binder.align(10, Alignment::NewPage), Ok(17));
+    }
+
+    #[test]
+    fn test_binder_errors() {
+        let mut binder = Binder::new(3, 3, 20);
+        assert_eq!(
+            binder.set_rows(0),
+            Err(BinderError::ZeroDimension(Dimension::Rows))
+        );
+        assert_eq!(
+            Binder::try_new(3, 0, 20),
+            Err(BinderError::ZeroDimension(Dimension::Columns))
+        );
+
+        let huge = Binder::new(u16::MAX.into(), u16::MAX.into(), 2);
+        assert_eq!(huge.capacity(), Err(BinderError::Overflow));
+        assert_eq!(
+            BinderSlot::try_from_index(&huge, SlotIndex::new(0)),
+            Err(BinderError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_try_from_index() {
+        let binder = Binder::new(3, 3, 20);
+        assert!(BinderSlot::try_from_index(&binder, SlotIndex::new(179)).is_ok());
+        assert_eq!(
+            BinderSlot::try_from_index(&binder, SlotIndex::new(180)),
+            Err(BinderError::BeyondCapacity {
+                index: 180,
+                capacity: 180
+            })
+        );
     }
 }
@@ -0,0 +1,434 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::card_number::CardNumber;
+
+/// Errors that can occur when parsing or placing a card identifier.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum CardIdError {
+    /// The identifier was empty.
+    #[error("Card ID should not be empty")]
+    Empty,
+    /// The identifier has no digits.
+    #[error("Card ID {0:?} has no number")]
+    MissingNumber(String),
+    /// The number in the identifier does not fit in a `u32`.
+    #[error("Card ID {0:?} has a number that is too large")]
+    InvalidNumber(String),
+    /// The prefix does not match the main set or any subset.
+    #[error("Unknown card ID prefix {0:?}")]
+    UnknownPrefix(String),
+    /// The suffix is not one of the variants allowed for its prefix.
+    #[error("Unknown card ID suffix {0:?}")]
+    UnknownSuffix(String),
+    /// The number lies outside the main set or subset.
+    #[error("Card number {number} is outside the range 1..={size}")]
+    OutOfRange { number: u32, size: u32 },
+    /// The identifier maps past the largest card number a `u32` can hold.
+    #[error("Card ID {0:?} is past the largest card number")]
+    Overflow(String),
+}
+
+/// A printed card identifier, such as "025", "TG05", "123a", "SV49★" or "LOB-EN001".
+///
+/// An identifier is a prefix, a number and a suffix. Prefixes are compared
+/// case-insensitively and stored in upper case; suffixes are stored in lower case.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CardId {
+    prefix: String,
+    number: u32,
+    suffix: String,
+    /// The minimum number of digits to print the number with.
+    digits: u8,
+}
+
+/// How the identifiers of one run of cards are written.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct IdFormat {
+    /// The prefix before the number, such as "TG" or "LOB-EN".
+    prefix: String,
+    /// The minimum number of digits to print numbers with.
+    digits: u8,
+    /// The variant suffixes each number may have, such as "a" and "b".
+    ///
+    /// Each number takes one ordinal for the base card followed by one per suffix.
+    suffixes: Vec<String>,
+}
+
+/// A run of cards numbered separately from the main set, such as a trainer gallery.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct Subset {
+    format: IdFormat,
+    /// The number of cards in the subset.
+    size: u32,
+}
+
+/// Rules for mapping card identifiers onto card numbers.
+///
+/// The main set comes first, followed by each subset in order.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct CardIdScheme {
+    main: IdFormat,
+    subsets: Vec<Subset>,
+}
+
+impl CardId {
+    /// Create a new card identifier.
+    pub fn new(prefix: &str, number: u32, suffix: &str) -> Self {
+        Self {
+            prefix: prefix.to_uppercase(),
+            number,
+            suffix: suffix.to_lowercase(),
+            digits: 0,
+        }
+    }
+
+    /// Get the prefix before the number.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the number.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Get the suffix after the number.
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+}
+
+// Leading zeros are only kept for display, so "TG5" and "TG05" are the same card.
+impl PartialEq for CardId {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.prefix, self.number, &self.suffix) == (&other.prefix, other.number, &other.suffix)
+    }
+}
+
+impl Eq for CardId {}
+
+impl FromStr for CardId {
+    type Err = CardIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(CardIdError::Empty);
+        }
+
+        let start = s
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(|| CardIdError::MissingNumber(s.to_owned()))?;
+        let end = s[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(s.len(), |end| start + end);
+
+        let digits = &s[start..end];
+        let number = digits
+            .parse()
+            .map_err(|_| CardIdError::InvalidNumber(s.to_owned()))?;
+
+        Ok(Self {
+            digits: digits.len().try_into().unwrap_or(u8::MAX),
+            ..Self::new(&s[..start], number, &s[end..])
+        })
+    }
+}
+
+impl fmt::Display for CardId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{:0width$}{}",
+            self.prefix,
+            self.number,
+            self.suffix,
+            width = self.digits as usize
+        )
+    }
+}
+
+impl IdFormat {
+    /// Create a new format with the given prefix and no variant suffixes.
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_uppercase(),
+            ..Self::default()
+        }
+    }
+
+    /// Get the prefix before the number.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Update the prefix before the number.
+    pub fn set_prefix(&mut self, prefix: &str) {
+        self.prefix = prefix.trim().to_uppercase();
+    }
+
+    /// Get the minimum number of digits to print numbers with.
+    pub fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    /// Update the minimum number of digits to print numbers with.
+    pub fn set_digits(&mut self, digits: u8) {
+        self.digits = digits;
+    }
+
+    /// Get the variant suffixes each number may have.
+    pub fn suffixes(&self) -> &[String] {
+        &self.suffixes
+    }
+
+    /// Update the variant suffixes each number may have. Empty suffixes are ignored.
+    pub fn set_suffixes<S: AsRef<str>>(&mut self, suffixes: impl IntoIterator<Item = S>) {
+        self.suffixes = suffixes
+            .into_iter()
+            .map(|suffix| suffix.as_ref().trim().to_lowercase())
+            .filter(|suffix| !suffix.is_empty())
+            .collect();
+    }
+
+    /// The number of ordinals each number takes.
+    fn stride(&self) -> u32 {
+        self.suffixes.len() as u32 + 1
+    }
+
+    /// The number of ordinals taken by `size` numbers.
+    fn ordinals(&self, size: u32) -> u32 {
+        size.saturating_mul(self.stride())
+    }
+
+    /// Get the 0-based offset of an identifier within a run of `size` numbers.
+    fn offset_of(&self, id: &CardId, size: u32) -> Result<u32, CardIdError> {
+        if !(1..=size).contains(&id.number) {
+            return Err(CardIdError::OutOfRange {
+                number: id.number,
+                size,
+            });
+        }
+
+        let variant = if id.suffix.is_empty() {
+            0
+        } else {
+            self.suffixes
+                .iter()
+                .position(|suffix| *suffix == id.suffix)
+                .ok_or_else(|| CardIdError::UnknownSuffix(id.suffix.clone()))?
+                + 1
+        };
+
+        (id.number - 1)
+            .checked_mul(self.stride())
+            .and_then(|offset| offset.checked_add(variant as u32))
+            .ok_or_else(|| CardIdError::Overflow(id.to_string()))
+    }
+
+    /// Get the identifier at a 0-based offset within the run.
+    fn card_id(&self, offset: u32) -> CardId {
+        let variant = (offset % self.stride()) as usize;
+        let suffix = variant
+            .checked_sub(1)
+            .map_or("", |variant| &self.suffixes[variant]);
+
+        CardId {
+            digits: self.digits,
+            ..CardId::new(&self.prefix, offset / self.stride() + 1, suffix)
+        }
+    }
+}
+
+impl Subset {
+    /// Create a new subset of `size` cards written with the given prefix.
+    pub fn new(prefix: &str, size: u32) -> Self {
+        Self {
+            format: IdFormat::new(prefix),
+            size,
+        }
+    }
+
+    /// Get how the subset's identifiers are written.
+    pub fn format(&self) -> &IdFormat {
+        &self.format
+    }
+
+    /// Get how the subset's identifiers are written for editing.
+    pub fn format_mut(&mut self) -> &mut IdFormat {
+        &mut self.format
+    }
+
+    /// Get the number of cards in the subset.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Update the number of cards in the subset.
+    pub fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+}
+
+impl CardIdScheme {
+    /// Get how the main set's identifiers are written.
+    pub fn main(&self) -> &IdFormat {
+        &self.main
+    }
+
+    /// Get how the main set's identifiers are written for editing.
+    pub fn main_mut(&mut self) -> &mut IdFormat {
+        &mut self.main
+    }
+
+    /// Get the subsets placed after the main set, in order.
+    pub fn subsets(&self) -> &[Subset] {
+        &self.subsets
+    }
+
+    /// Get the subsets placed after the main set for editing.
+    pub fn subsets_mut(&mut self) -> &mut Vec<Subset> {
+        &mut self.subsets
+    }
+
+    /// The runs of cards in order, with the number of cards in each.
+    fn runs(&self, main_size: u32) -> impl Iterator<Item = (&IdFormat, u32)> {
+        std::iter::once((&self.main, main_size)).chain(
+            self.subsets
+                .iter()
+                .map(|subset| (&subset.format, subset.size)),
+        )
+    }
+
+    /// Get the number of card numbers needed for a main set of `main_size` cards and every subset.
+    pub fn total(&self, main_size: u32) -> u32 {
+        self.runs(main_size).fold(0, |total, (format, size)| {
+            total.saturating_add(format.ordinals(size))
+        })
+    }
+
+    /// Check if a card number falls in the main set rather than a subset.
+    pub fn in_main_set(&self, card_number: CardNumber, main_size: u32) -> bool {
+        card_number.to_index().get() < self.main.ordinals(main_size)
+    }
+
+    /// Map an identifier onto its card number, given the size of the main set.
+    pub fn card_number(&self, id: &CardId, main_size: u32) -> Result<CardNumber, CardIdError> {
+        let mut start = 0u32;
+        for (format, size) in self.runs(main_size) {
+            if format.prefix == id.prefix {
+                let offset = format.offset_of(id, size)?;
+                let number = start
+                    .checked_add(offset)
+                    .and_then(|index| index.checked_add(1))
+                    .ok_or_else(|| CardIdError::Overflow(id.to_string()))?;
+                let total = self.total(main_size);
+                return CardNumber::try_new(number, total).ok_or(CardIdError::OutOfRange {
+                    number: id.number,
+                    size,
+                });
+            }
+
+            start = start.saturating_add(format.ordinals(size));
+        }
+
+        Err(CardIdError::UnknownPrefix(id.prefix.clone()))
+    }
+
+    /// Parse an identifier and map it onto its card number, given the size of the main set.
+    pub fn parse(&self, s: &str, main_size: u32) -> Result<CardNumber, CardIdError> {
+        self.card_number(&s.parse()?, main_size)
+    }
+
+    /// Get the identifier of a card number, given the size of the main set.
+    pub fn card_id(&self, card_number: CardNumber, main_size: u32) -> Option<CardId> {
+        let mut offset = card_number.to_index().get();
+        for (format, size) in self.runs(main_size) {
+            let ordinals = format.ordinals(size);
+            if offset < ordinals {
+                return Some(format.card_id(offset));
+            }
+
+            offset -= ordinals;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_card_id() {
+        let id: CardId = "tg05".parse().unwrap();
+        assert_eq!((id.prefix(), id.number(), id.suffix()), ("TG", 5, ""));
+        assert_eq!(id.to_string(), "TG05");
+        assert_eq!(id, CardId::new("TG", 5, ""));
+
+        let id: CardId = "LOB-EN001".parse().unwrap();
+        assert_eq!((id.prefix(), id.number(), id.suffix()), ("LOB-EN", 1, ""));
+
+        let id: CardId = "123a".parse().unwrap();
+        assert_eq!((id.prefix(), id.number(), id.suffix()), ("", 123, "a"));
+
+        let id: CardId = "SV49★".parse().unwrap();
+        assert_eq!((id.prefix(), id.number(), id.suffix()), ("SV", 49, "★"));
+
+        assert_eq!("".parse::<CardId>(), Err(CardIdError::Empty));
+        assert_eq!(
+            "★".parse::<CardId>(),
+            Err(CardIdError::MissingNumber("★".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_scheme() {
+        let mut scheme = CardIdScheme::default();
+        scheme.main_mut().set_suffixes(["a"]);
+        scheme.subsets_mut().push(Subset::new("TG", 30));
+        scheme.subsets_mut().push(Subset::new("GG", 70));
+
+        assert_eq!(scheme.total(10), 20 + 30 + 70);
+        assert_eq!(scheme.parse("3", 10).unwrap().get(), 5);
+        assert_eq!(scheme.parse("3a", 10).unwrap().get(), 6);
+        assert_eq!(scheme.parse("TG01", 10).unwrap().get(), 21);
+        assert_eq!(scheme.parse("gg70", 10).unwrap().get(), 120);
+
+        assert_eq!(
+            scheme.parse("11", 10),
+            Err(CardIdError::OutOfRange {
+                number: 11,
+                size: 10
+            })
+        );
+        assert_eq!(
+            scheme.parse("3b", 10),
+            Err(CardIdError::UnknownSuffix("b".to_owned()))
+        );
+        assert_eq!(
+            scheme.parse("SV1", 10),
+            Err(CardIdError::UnknownPrefix("SV".to_owned()))
+        );
+
+        for n in 1..=scheme.total(10) {
+            let card_number = CardNumber::try_new(n, n).unwrap();
+            let id = scheme.card_id(card_number, 10).unwrap();
+            assert_eq!(scheme.card_number(&id, 10), Ok(card_number));
+        }
+
+        // Identifiers past the largest card number are errors rather than wrapping
+        assert_eq!(
+            scheme.parse("4294967295a", u32::MAX),
+            Err(CardIdError::Overflow("4294967295a".to_owned()))
+        );
+        assert_eq!(
+            scheme.parse("TG01", u32::MAX),
+            Err(CardIdError::Overflow("TG01".to_owned()))
+        );
+    }
+}
@@ -42,6 +42,11 @@ impl SlotIndex {
     pub fn get(self) -> u32 {
         self.0
     }
+
+    /// Convert to a 1-based card number.
+    pub fn to_card_number(self) -> CardNumber {
+        CardNumber(NonZeroU32::MIN.saturating_add(self.0))
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +67,7 @@ mod tests {
         for n in 1..=max {
             let cn = CardNumber::try_new(n, max).unwrap();
             assert_eq!(cn.to_index().get(), n - 1);
+            assert_eq!(cn.to_index().to_card_number(), cn);
         }
     }
 }
@@ -0,0 +1,589 @@
+use std::{fmt::Write as _, path::Path};
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+use thiserror::Error;
+
+use crate::{
+    binder::{BinderError, Pocket},
+    collection::Collection,
+    set_layout::SetLayout,
+};
+
+/// The width of a printed page in points, for A4 paper.
+const PAGE_WIDTH: f32 = 595.0;
+/// The height of a printed page in points, for A4 paper.
+const PAGE_HEIGHT: f32 = 842.0;
+/// The blank space around the edge of a printed page, in points.
+const MARGIN: f32 = 36.0;
+/// The space between pockets, in points.
+const GAP: f32 = 6.0;
+/// The width of a card over its height.
+const CARD_RATIO: f32 = 2.5 / 3.5;
+
+/// What belongs in a pocket on a checklist page.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ChecklistPocket {
+    /// No card belongs in the pocket.
+    Empty,
+    /// The pocket is reserved, so card numbering skips it.
+    Reserved,
+    /// A card belongs in the pocket.
+    Card {
+        /// The label printed on the card, such as "25/165" or "TG05".
+        label: String,
+        /// The name of the card, if known.
+        name: Option<String>,
+        owned: bool,
+    },
+}
+
+/// A binder page of a checklist, with what belongs in each pocket.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ChecklistPage {
+    /// The 1-based number of the binder.
+    binder: u32,
+    /// The 1-based page within the binder.
+    page: u32,
+    rows: u32,
+    cols: u32,
+    /// The pockets on the page, row by row.
+    pockets: Vec<ChecklistPocket>,
+}
+
+/// A printable checklist of every page of every binder in a collection.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Checklist {
+    pages: Vec<ChecklistPage>,
+}
+
+/// The file formats a checklist can be written as.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChecklistFormat {
+    /// A single image with the pages one above the other.
+    Svg,
+    /// A document with one binder page per printed page.
+    Pdf,
+}
+
+/// Errors that can occur when writing a checklist.
+#[derive(Debug, Error)]
+pub enum ChecklistError {
+    /// The file extension is not one of the supported formats.
+    #[error("Unknown checklist format {0:?}, expected .svg or .pdf")]
+    UnknownFormat(String),
+    /// The file could not be written.
+    #[error("Cannot write checklist: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Something drawn on a printed page, measured in points from the top left corner.
+#[derive(Debug, PartialEq, Clone)]
+enum Shape {
+    /// The outline of a rectangle, in a shade of grey from black at 0 to white at 1.
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        gray: f32,
+    },
+    /// A black line through a series of points.
+    Line { points: Vec<(f32, f32)> },
+    /// A line of text, with its baseline starting at `x` and `y`.
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        gray: f32,
+        text: String,
+    },
+}
+
+impl ChecklistPage {
+    /// Get the 1-based number of the binder.
+    pub fn binder(&self) -> u32 {
+        self.binder
+    }
+
+    /// Get the 1-based page within the binder.
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Get the number of rows of pockets on the page.
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Get the number of columns of pockets on the page.
+    pub fn cols(&self) -> u32 {
+        self.cols
+    }
+
+    /// Get the pockets on the page, row by row.
+    pub fn pockets(&self) -> &[ChecklistPocket] {
+        &self.pockets
+    }
+
+    /// Lay out the page as shapes to draw.
+    fn shapes(&self) -> Vec<Shape> {
+        let cards = self
+            .pockets
+            .iter()
+            .filter(|pocket| matches!(pocket, ChecklistPocket::Card { .. }))
+            .count();
+        let owned = self
+            .pockets
+            .iter()
+            .filter(|pocket| matches!(pocket, ChecklistPocket::Card { owned: true, .. }))
+            .count();
+
+        let title_size = 14.0;
+        let mut shapes = vec![Shape::Text {
+            x: MARGIN,
+            y: MARGIN + title_size,
+            size: title_size,
+            gray: 0.0,
+            text: format!(
+                "Binder {}, page {} ({owned} of {cards} owned)",
+                self.binder, self.page
+            ),
+        }];
+
+        // Pockets are as large as fits the page, keeping the shape of a card
+        let top = MARGIN + title_size + 12.0;
+        let (rows, cols) = (self.rows.max(1) as f32, self.cols.max(1) as f32);
+        let cell_width = (PAGE_WIDTH - 2.0 * MARGIN - GAP * (cols - 1.0)) / cols;
+        let cell_height = (PAGE_HEIGHT - top - MARGIN - GAP * (rows - 1.0)) / rows;
+        let width = cell_width.min(cell_height * CARD_RATIO);
+        let height = width / CARD_RATIO;
+        let left = (PAGE_WIDTH - width * cols - GAP * (cols - 1.0)) / 2.0;
+
+        for (i, pocket) in self.pockets.iter().enumerate() {
+            let (row, col) = ((i as u32 / self.cols) as f32, (i as u32 % self.cols) as f32);
+            let x = left + col * (width + GAP);
+            let y = top + row * (height + GAP);
+
+            let gray = match pocket {
+                ChecklistPocket::Card { .. } => 0.0,
+                _ => 0.75,
+            };
+            shapes.push(Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                gray,
+            });
+
+            match pocket {
+                ChecklistPocket::Empty => {}
+                ChecklistPocket::Reserved => shapes.push(Shape::Text {
+                    x: x + 6.0,
+                    y: y + 15.0,
+                    size: 9.0,
+                    gray: 0.5,
+                    text: "Reserved".to_owned(),
+                }),
+                ChecklistPocket::Card { label, name, owned } => {
+                    shapes.push(Shape::Rect {
+                        x: x + 6.0,
+                        y: y + 6.0,
+                        width: 10.0,
+                        height: 10.0,
+                        gray: 0.0,
+                    });
+                    if *owned {
+                        shapes.push(Shape::Line {
+                            points: vec![
+                                (x + 8.0, y + 11.0),
+                                (x + 10.5, y + 14.0),
+                                (x + 14.0, y + 8.0),
+                            ],
+                        });
+                    }
+
+                    shapes.push(Shape::Text {
+                        x: x + 22.0,
+                        y: y + 15.0,
+                        size: 10.0,
+                        gray: 0.0,
+                        text: format!("#{label}"),
+                    });
+
+                    let name_size = 8.0;
+                    let max_lines = ((height - 30.0) / (name_size + 2.0)).max(0.0) as usize;
+                    let max_chars = ((width - 12.0) / (name_size * 0.5)).max(1.0) as usize;
+                    let lines = wrap(name.as_deref().unwrap_or_default(), max_chars, max_lines);
+                    for (line, text) in lines.into_iter().enumerate() {
+                        shapes.push(Shape::Text {
+                            x: x + 6.0,
+                            y: y + 30.0 + line as f32 * (name_size + 2.0),
+                            size: name_size,
+                            gray: 0.0,
+                            text,
+                        });
+                    }
+                }
+            }
+        }
+
+        shapes
+    }
+}
+
+impl Checklist {
+    /// Build a checklist of every page of every binder in a collection,
+    /// with the cards of each set where they belong.
+    pub fn new(collection: &Collection, sets: &SetLayout) -> Result<Self, BinderError> {
+        let mut pages = Vec::new();
+        for (number, binder) in (1..).zip(collection.binders()) {
+            for page in 1..=binder.pages() {
+                let (rows, cols) = (binder.rows_on(page), binder.cols_on(page));
+                let mut pockets = Vec::new();
+                for row in 1..=rows {
+                    for col in 1..=cols {
+                        if binder.is_reserved(Pocket::new(page, row, col)) {
+                            pockets.push(ChecklistPocket::Reserved);
+                            continue;
+                        }
+
+                        let slot = collection.from_position(number, page, row, col)?;
+                        let pocket = match sets.card_at(collection, slot.index())? {
+                            Some((set, card)) => {
+                                let placed = sets.set(set).expect("Located set should exist");
+                                ChecklistPocket::Card {
+                                    label: placed.set().label(card),
+                                    name: placed.set().card_name(card).map(str::to_owned),
+                                    owned: placed.ownership().is_owned(card),
+                                }
+                            }
+                            None => ChecklistPocket::Empty,
+                        };
+                        pockets.push(pocket);
+                    }
+                }
+
+                pages.push(ChecklistPage {
+                    binder: number,
+                    page,
+                    rows,
+                    cols,
+                    pockets,
+                });
+            }
+        }
+
+        Ok(Self { pages })
+    }
+
+    /// Get the pages of the checklist, binder by binder.
+    pub fn pages(&self) -> &[ChecklistPage] {
+        &self.pages
+    }
+
+    /// Render the checklist as an SVG image, with the pages one above the other.
+    pub fn to_svg(&self) -> String {
+        let height = PAGE_HEIGHT * self.pages.len().max(1) as f32;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{PAGE_WIDTH}pt\" \
+             height=\"{height}pt\" viewBox=\"0 0 {PAGE_WIDTH} {height}\" \
+             font-family=\"Helvetica, Arial, sans-serif\">\n"
+        );
+
+        for (i, page) in self.pages.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                "<g transform=\"translate(0 {})\">\n\
+                 <rect width=\"{PAGE_WIDTH}\" height=\"{PAGE_HEIGHT}\" fill=\"white\" stroke=\"#cccccc\"/>",
+                i as f32 * PAGE_HEIGHT
+            );
+
+            for shape in page.shapes() {
+                let _ = match shape {
+                    Shape::Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                        gray,
+                    } => writeln!(
+                        svg,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" \
+                         fill=\"none\" stroke=\"{}\"/>",
+                        svg_gray(gray)
+                    ),
+                    Shape::Line { points } => {
+                        let points: Vec<String> =
+                            points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+                        writeln!(
+                            svg,
+                            "<polyline points=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"1.5\"/>",
+                            points.join(" ")
+                        )
+                    }
+                    Shape::Text {
+                        x,
+                        y,
+                        size,
+                        gray,
+                        text,
+                    } => writeln!(
+                        svg,
+                        "<text x=\"{x}\" y=\"{y}\" font-size=\"{size}\" fill=\"{}\">{}</text>",
+                        svg_gray(gray),
+                        escape_xml(&text)
+                    ),
+                };
+            }
+
+            svg.push_str("</g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Render the checklist as a PDF document, with one binder page per printed page.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let font_id = Ref::new(3);
+        let font_name = Name(b"F1");
+
+        // Each page is followed by its content stream
+        let page_ids: Vec<Ref> = (0..self.pages.len() as i32)
+            .map(|i| Ref::new(4 + 2 * i))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+        pdf.type1_font(font_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        for (page, &page_id) in self.pages.iter().zip(&page_ids) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut writer = pdf.page(page_id);
+            writer
+                .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(page_tree_id)
+                .contents(content_id);
+            writer.resources().fonts().pair(font_name, font_id);
+            writer.finish();
+
+            // PDF measures up from the bottom of the page
+            let mut content = Content::new();
+            for shape in page.shapes() {
+                match shape {
+                    Shape::Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                        gray,
+                    } => {
+                        content
+                            .set_stroke_gray(gray)
+                            .set_line_width(1.0)
+                            .rect(x, PAGE_HEIGHT - y - height, width, height)
+                            .stroke();
+                    }
+                    Shape::Line { points } => {
+                        content.set_stroke_gray(0.0).set_line_width(1.5);
+                        for (i, &(x, y)) in points.iter().enumerate() {
+                            if i == 0 {
+                                content.move_to(x, PAGE_HEIGHT - y);
+                            } else {
+                                content.line_to(x, PAGE_HEIGHT - y);
+                            }
+                        }
+                        content.stroke();
+                    }
+                    Shape::Text {
+                        x,
+                        y,
+                        size,
+                        gray,
+                        text,
+                    } => {
+                        content
+                            .set_fill_gray(gray)
+                            .begin_text()
+                            .set_font(font_name, size)
+                            .next_line(x, PAGE_HEIGHT - y)
+                            .show(Str(&win_ansi(&text)))
+                            .end_text();
+                    }
+                }
+            }
+
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+
+    /// Write the checklist to a `.svg` or `.pdf` file.
+    pub fn write(&self, path: &Path) -> Result<(), ChecklistError> {
+        match ChecklistFormat::from_path(path)? {
+            ChecklistFormat::Svg => std::fs::write(path, self.to_svg())?,
+            ChecklistFormat::Pdf => std::fs::write(path, self.to_pdf())?,
+        }
+
+        Ok(())
+    }
+}
+
+impl ChecklistFormat {
+    /// Guess the format of a checklist from its file extension.
+    pub fn from_path(path: &Path) -> Result<Self, ChecklistError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+
+        match extension.to_ascii_lowercase().as_str() {
+            "svg" => Ok(Self::Svg),
+            "pdf" => Ok(Self::Pdf),
+            _ => Err(ChecklistError::UnknownFormat(extension.to_owned())),
+        }
+    }
+}
+
+/// Wrap text into at most `max_lines` lines of at most `max_chars` characters,
+/// breaking between words where possible and marking cut off text with "...".
+fn wrap(text: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_chars => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => {
+                // Words too long for a line are split across lines
+                let chars: Vec<char> = word.chars().collect();
+                lines.extend(chars.chunks(max_chars).map(|chunk| chunk.iter().collect()));
+            }
+        }
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(line) = lines.last_mut() {
+            let kept: String = line.chars().take(max_chars.saturating_sub(3)).collect();
+            *line = format!("{kept}...");
+        }
+    }
+
+    lines
+}
+
+/// Convert a shade of grey from black at 0 to white at 1 to an SVG colour.
+fn svg_gray(gray: f32) -> String {
+    let level = (gray.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{level:02x}{level:02x}{level:02x}")
+}
+
+/// Escape the characters with special meaning in XML text.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Encode text for the standard PDF fonts, replacing characters they lack with "?".
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            code @ (0x20..=0x7e | 0xa0..=0xff) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binder::{Alignment, Binder},
+        card_number::CardNumber,
+        metadata::{SetList, SetListFormat},
+        set_definition::SetDefinition,
+        set_layout::PlacedSet,
+    };
+
+    fn checklist() -> Checklist {
+        let mut binder = Binder::new(3, 3, 2);
+        binder.reserve(Pocket::new(2, 3, 3)).unwrap();
+        let collection = Collection::new(binder);
+
+        let mut set = SetDefinition::new("", 10, 10);
+        let list = SetList::parse("number,name\n1,Bulbasaur\n", SetListFormat::Csv).unwrap();
+        assert!(set.attach(&list).is_empty());
+
+        let mut placed = PlacedSet::new(set, Alignment::Continue);
+        placed
+            .ownership_mut()
+            .toggle(CardNumber::try_new(2, u32::MAX).unwrap());
+        Checklist::new(&collection, &SetLayout::new(placed)).unwrap()
+    }
+
+    #[test]
+    fn test_checklist() {
+        let checklist = checklist();
+        assert_eq!(checklist.pages().len(), 2);
+
+        let pockets = checklist.pages()[0].pockets();
+        assert_eq!(
+            pockets[0],
+            ChecklistPocket::Card {
+                label: "1/10".to_owned(),
+                name: Some("Bulbasaur".to_owned()),
+                owned: false
+            }
+        );
+        assert!(matches!(
+            pockets[1],
+            ChecklistPocket::Card { owned: true, .. }
+        ));
+
+        // Card 10 starts page 2, the rest of which is empty apart from the reserved pocket
+        let pockets = checklist.pages()[1].pockets();
+        assert!(matches!(&pockets[0], ChecklistPocket::Card { label, .. } if label == "10/10"));
+        assert_eq!(pockets[1], ChecklistPocket::Empty);
+        assert_eq!(pockets[8], ChecklistPocket::Reserved);
+    }
+
+    #[test]
+    fn test_render() {
+        let checklist = checklist();
+
+        let svg = checklist.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">Binder 1, page 1 (1 of 9 owned)</text>"));
+        assert!(svg.contains(">#1/10</text>"));
+        assert_eq!(svg.matches("<polyline").count(), 1);
+
+        let pdf = checklist.to_pdf();
+        assert!(pdf.starts_with(b"%PDF"));
+
+        assert!(matches!(
+            ChecklistFormat::from_path(Path::new("checklist.png")),
+            Err(ChecklistError::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("Mr. Mime", 10, 3), ["Mr. Mime"]);
+        assert_eq!(wrap("Tapu Koko GX", 6, 3), ["Tapu", "Koko", "GX"]);
+        assert_eq!(wrap("Tapu Koko GX", 6, 2), ["Tapu", "Kok..."]);
+        assert_eq!(wrap("Crabominable", 5, 3), ["Crabo", "minab", "le"]);
+        assert_eq!(escape_xml("Nidoran <M>"), "Nidoran &lt;M&gt;");
+    }
+}
@@ -0,0 +1,436 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::{
+    binder::{Alignment, Binder, BinderSlot, FillOrder, PageSide, Pocket, Sidedness},
+    card_number::CardNumber,
+    checklist::Checklist,
+    collection::Collection,
+    metadata::{SetList, SetListError},
+    set_definition::SetDefinition,
+    set_layout::{PlacedSet, SetLayout},
+    sort_plan::{split_card_list, SortPlan},
+};
+
+/// Command line interface for TCG Bound.
+///
+/// Running without a subcommand launches the GUI.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// The binder layout shared by all subcommands.
+#[derive(Debug, Args)]
+pub struct BinderArgs {
+    /// The number of rows on each page.
+    #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    rows: u32,
+    /// The number of columns on each page.
+    #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    cols: u32,
+    /// The number of pages in the binder.
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    pages: u32,
+    /// The side of the first spread that page 1 is on.
+    #[arg(long, value_enum, default_value_t = SideArg::Right)]
+    first_page: SideArg,
+    /// Whether both sides of each sheet hold cards.
+    #[arg(long, value_enum, default_value_t = SidedArg::Double)]
+    sided: SidedArg,
+    /// The order in which the pockets on a page are filled.
+    #[arg(long, value_enum, default_value_t = FillOrderArg::RowMajor)]
+    fill_order: FillOrderArg,
+    /// A pocket that card numbering skips, as PAGE:ROW:COL. Can be given more than once.
+    #[arg(long, value_name = "PAGE:ROW:COL", value_parser = parse_pocket)]
+    reserve: Vec<Pocket>,
+}
+
+/// A side of an open binder.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SideArg {
+    Left,
+    Right,
+}
+
+/// Whether both sides of each sheet hold cards.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SidedArg {
+    Single,
+    Double,
+}
+
+/// The order in which the pockets on a page are filled.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FillOrderArg {
+    RowMajor,
+    RightToLeft,
+    ColumnMajor,
+    Serpentine,
+}
+
+/// Parse a pocket written as `PAGE:ROW:COL`.
+fn parse_pocket(s: &str) -> Result<Pocket, String> {
+    let parts = s
+        .split(':')
+        .map(|part| part.trim().parse::<u32>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match parts[..] {
+        [page, row, col] => Ok(Pocket::new(page, row, col)),
+        _ => Err(format!("Expected PAGE:ROW:COL, got {s:?}")),
+    }
+}
+
+impl BinderArgs {
+    fn binder(&self) -> Result<Binder> {
+        let mut binder = Binder::new(self.rows, self.cols, self.pages);
+        binder.set_first_page_side(match self.first_page {
+            SideArg::Left => PageSide::Left,
+            SideArg::Right => PageSide::Right,
+        });
+        binder.set_sidedness(match self.sided {
+            SidedArg::Single => Sidedness::SingleSided,
+            SidedArg::Double => Sidedness::DoubleSided,
+        });
+        binder.set_fill_order(match self.fill_order {
+            FillOrderArg::RowMajor => FillOrder::RowMajor,
+            FillOrderArg::RightToLeft => FillOrder::RightToLeft,
+            FillOrderArg::ColumnMajor => FillOrder::ColumnMajor,
+            FillOrderArg::Serpentine => FillOrder::Serpentine,
+        });
+
+        for &pocket in &self.reserve {
+            binder.reserve(pocket).with_context(|| {
+                format!(
+                    "Cannot reserve page {}, row {}, column {}",
+                    pocket.page(),
+                    pocket.row(),
+                    pocket.col()
+                )
+            })?;
+        }
+
+        Ok(binder)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the binder location of one or more card numbers.
+    Locate {
+        #[command(flatten)]
+        binder: BinderArgs,
+        /// The card numbers to locate.
+        #[arg(required = true, value_parser = clap::value_parser!(u32).range(1..))]
+        cards: Vec<u32>,
+    },
+    /// Print the card number that belongs in a binder pocket.
+    Lookup {
+        #[command(flatten)]
+        binder: BinderArgs,
+        /// The 1-based page number.
+        page: u32,
+        /// The 1-based row on the page.
+        row: u32,
+        /// The 1-based column on the page.
+        col: u32,
+    },
+    /// Plan the order to insert a pile of cards, grouped by spread from front to back.
+    Plan {
+        #[command(flatten)]
+        binder: BinderArgs,
+        /// A file listing card numbers, separated by whitespace or commas.
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// The card numbers to insert.
+        #[arg(required_unless_present = "file", value_parser = clap::value_parser!(u32).range(1..))]
+        cards: Vec<u32>,
+    },
+    /// Write a printable checklist of every binder page as an SVG or PDF file.
+    Checklist {
+        #[command(flatten)]
+        binder: BinderArgs,
+        /// The number of cards in the set, if fewer than the binder holds.
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+        total: Option<u32>,
+        /// A JSON or CSV set list with the name of each card.
+        #[arg(short, long)]
+        set_list: Option<PathBuf>,
+        /// The file to write, ending in .svg or .pdf.
+        output: PathBuf,
+    },
+}
+
+impl Command {
+    /// Run the command, writing its output to `out`.
+    pub fn run(&self, out: &mut impl Write) -> Result<()> {
+        match self {
+            Command::Locate { binder, cards } => locate(&binder.binder()?, cards, out),
+            Command::Lookup {
+                binder,
+                page,
+                row,
+                col,
+            } => lookup(&binder.binder()?, *page, *row, *col, out),
+            Command::Plan {
+                binder,
+                file,
+                cards,
+            } => {
+                let mut cards = cards.clone();
+                if let Some(file) = file {
+                    let list = std::fs::read_to_string(file)
+                        .with_context(|| format!("Cannot read {}", file.display()))?;
+                    for token in split_card_list(&list) {
+                        cards.push(
+                            token
+                                .parse()
+                                .with_context(|| format!("Invalid card number {token:?}"))?,
+                        );
+                    }
+                }
+
+                plan(&binder.binder()?, &cards, out)
+            }
+            Command::Checklist {
+                binder,
+                total,
+                set_list,
+                output,
+            } => checklist(&binder.binder()?, *total, set_list.as_deref(), output, out),
+        }
+    }
+}
+
+fn locate(binder: &Binder, cards: &[u32], out: &mut impl Write) -> Result<()> {
+    for &card in cards {
+        let card_number =
+            CardNumber::try_new(card, u32::MAX).context("Card number should be non-zero")?;
+        let slot = BinderSlot::try_from_card_number(binder, card_number)
+            .with_context(|| format!("Cannot locate card {card}"))?;
+        writeln!(
+            out,
+            "Card {}: page {}, row {}, column {}, slot {}",
+            card_number.get(),
+            slot.page(),
+            slot.row(),
+            slot.col(),
+            slot.slot_on_page(binder),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn lookup(binder: &Binder, page: u32, row: u32, col: u32, out: &mut impl Write) -> Result<()> {
+    let slot = BinderSlot::from_position(binder, page, row, col)?;
+    let card_number = slot
+        .card_number(binder)
+        .with_context(|| format!("Page {page}, row {row}, column {col} is reserved"))?;
+    writeln!(out, "{}", card_number.get())?;
+
+    Ok(())
+}
+
+fn plan(binder: &Binder, cards: &[u32], out: &mut impl Write) -> Result<()> {
+    let card_numbers = cards
+        .iter()
+        .map(|&card| CardNumber::try_new(card, u32::MAX).context("Card number should be non-zero"))
+        .collect::<Result<Vec<_>>>()?;
+
+    let plan = SortPlan::new(&Collection::new(binder.clone()), card_numbers);
+    for group in plan.groups() {
+        writeln!(out, "{group}")?;
+    }
+    for (card, e) in plan.unplaced() {
+        writeln!(out, "Card {} does not fit: {e}", card.get())?;
+    }
+
+    Ok(())
+}
+
+fn checklist(
+    binder: &Binder,
+    total: Option<u32>,
+    set_list: Option<&Path>,
+    output: &Path,
+    out: &mut impl Write,
+) -> Result<()> {
+    let collection = Collection::new(binder.clone());
+    let total = match total {
+        Some(total) => total,
+        None => collection.capacity()?,
+    };
+
+    let set = SetDefinition::new("", total, total);
+    let mut sets = SetLayout::new(PlacedSet::new(set, Alignment::Continue));
+    if let Some(path) = set_list {
+        let list = SetList::read(path)?;
+        for e in sets.attach(&collection, 0, &list) {
+            match e {
+                SetListError::InvalidCard { .. } => writeln!(out, "Skipped {e}")?,
+                _ => writeln!(out, "{e}")?,
+            }
+        }
+    }
+
+    let checklist = Checklist::new(&collection, &sets)?;
+    checklist
+        .write(output)
+        .with_context(|| format!("Cannot write {}", output.display()))?;
+    writeln!(
+        out,
+        "Wrote {} pages to {}",
+        checklist.pages().len(),
+        output.display()
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let cli = Cli::parse_from(["tcg-bound", "locate", "-r", "3", "-c", "3", "1", "10", "18"]);
+        let mut out = Vec::new();
+        cli.command.unwrap().run(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Card 1: page 1, row 1, column 1, slot 1\n\
+             Card 10: page 2, row 1, column 1, slot 1\n\
+             Card 18: page 2, row 3, column 3, slot 9\n"
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let cli = Cli::parse_from(["tcg-bound", "lookup", "2", "1", "2"]);
+        let mut out = Vec::new();
+        cli.command.unwrap().run(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "11\n");
+    }
+
+    #[test]
+    fn test_plan() {
+        let cli = Cli::parse_from(["tcg-bound", "plan", "-p", "3", "30", "3", "12", "11", "18"]);
+        let mut out = Vec::new();
+        cli.command.unwrap().run(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Binder 1, page 1: slot 3 (#3)\n\
+             Binder 1, pages 2–3: page 2 slot 2 (#11), slot 3 (#12), slot 9 (#18)\n\
+             Card 30 does not fit: Position 30 is beyond the capacity of 27 cards\n"
+        );
+    }
+
+    #[test]
+    fn test_binder_options() {
+        let run = |args: &[&str]| {
+            let cli = Cli::parse_from(["tcg-bound"].iter().chain(args));
+            let mut out = Vec::new();
+            cli.command
+                .unwrap()
+                .run(&mut out)
+                .map(|()| String::from_utf8(out).unwrap())
+        };
+
+        assert_eq!(
+            run(&[
+                "locate",
+                "--reserve",
+                "1:1:1",
+                "--fill-order",
+                "column-major",
+                "1"
+            ])
+            .unwrap(),
+            "Card 1: page 1, row 2, column 1, slot 2\n"
+        );
+        assert_eq!(
+            run(&["lookup", "--reserve", "1:1:1", "1", "1", "2"]).unwrap(),
+            "1\n"
+        );
+        assert!(run(&["lookup", "--reserve", "1:1:1", "1", "1", "1"]).is_err());
+        assert!(run(&["locate", "--reserve", "1:4:1", "1"]).is_err());
+        assert!(Cli::try_parse_from(["tcg-bound", "locate", "--reserve", "1:1", "1"]).is_err());
+
+        // Spreads follow the first page side and sidedness
+        assert_eq!(
+            run(&["plan", "--first-page", "left", "1", "10", "19"]).unwrap(),
+            "Binder 1, pages 1–2: page 1 slot 1 (#1); page 2 slot 1 (#10)\n\
+             Binder 1, pages 3–4: page 3 slot 1 (#19)\n"
+        );
+        assert_eq!(
+            run(&["plan", "--sided", "single", "1", "10"]).unwrap(),
+            "Binder 1, page 1: slot 1 (#1)\n\
+             Binder 1, page 2: slot 1 (#10)\n"
+        );
+    }
+
+    #[test]
+    fn test_checklist() {
+        let output = std::env::temp_dir().join(format!(
+            "tcg-bound-test-checklist-{}.pdf",
+            std::process::id()
+        ));
+        let cli = Cli::parse_from([
+            "tcg-bound",
+            "checklist",
+            "-p",
+            "4",
+            output.to_str().unwrap(),
+        ]);
+        let mut out = Vec::new();
+        cli.command.unwrap().run(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("Wrote 4 pages to {}\n", output.display())
+        );
+        assert!(std::fs::read(&output).unwrap().starts_with(b"%PDF"));
+
+        let cli = Cli::parse_from([
+            "tcg-bound",
+            "checklist",
+            "-p",
+            "4",
+            "--first-page",
+            "left",
+            "--reserve",
+            "1:1:1",
+            output.to_str().unwrap(),
+        ]);
+        let mut out = Vec::new();
+        cli.command.unwrap().run(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("Wrote 4 pages to {}\n", output.display())
+        );
+        std::fs::remove_file(output).unwrap();
+
+        let cli = Cli::parse_from(["tcg-bound", "checklist", "checklist.png"]);
+        assert!(cli.command.unwrap().run(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_locate_beyond_capacity() {
+        let cli = Cli::parse_from(["tcg-bound", "locate", "-p", "1", "10"]);
+        let mut out = Vec::new();
+        assert!(cli.command.unwrap().run(&mut out).is_err());
+    }
+}
@@ -0,0 +1,430 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    binder::{Alignment, Binder, BinderError, BinderSlot, Dimension},
+    card_number::{CardNumber, SlotIndex},
+};
+use serde::{Deserialize, Serialize};
+
+/// An ordered list of binders, with card numbering continuing from one binder into the next.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(try_from = "CollectionData")]
+pub struct Collection {
+    binders: Vec<Binder>,
+}
+
+/// A collection as it is stored, checked to hold a binder before it is used.
+#[derive(Deserialize)]
+struct CollectionData {
+    binders: Vec<Binder>,
+}
+
+/// A struct to represent a card slot in one of the binders of a collection.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub struct CollectionSlot {
+    /// The 1-based number of the binder the card slot is in.
+    binder: NonZeroU32,
+    /// The card slot within that binder.
+    slot: BinderSlot,
+    /// The index of the card across the whole collection.
+    index: SlotIndex,
+}
+
+/// A card that moves forward one pocket to make room for an inserted card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Shift {
+    /// The card slot the card is in now.
+    from: CollectionSlot,
+    /// The card slot the card moves to, or `None` if it is pushed past the last page.
+    to: Option<CollectionSlot>,
+}
+
+/// The cards that shift forward when a card is inserted into a numbered run.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InsertPlan {
+    /// The card slot the inserted card goes in.
+    at: CollectionSlot,
+    /// The cards to shift, last card first, so each moves into an empty pocket.
+    shifts: Vec<Shift>,
+}
+
+/// Get the number of slots across a list of binders.
+fn total_capacity(binders: &[Binder]) -> Result<u32, BinderError> {
+    binders.iter().try_fold(0u32, |total, binder| {
+        total
+            .checked_add(binder.capacity()?)
+            .ok_or(BinderError::Overflow)
+    })
+}
+
+impl TryFrom<CollectionData> for Collection {
+    type Error = BinderError;
+
+    fn try_from(data: CollectionData) -> Result<Self, Self::Error> {
+        if data.binders.is_empty() {
+            return Err(BinderError::ZeroDimension(Dimension::Binders));
+        }
+
+        Ok(Self {
+            binders: data.binders,
+        })
+    }
+}
+
+impl Collection {
+    /// Create a new collection holding a single binder.
+    pub fn new(binder: Binder) -> Self {
+        Self {
+            binders: vec![binder],
+        }
+    }
+
+    /// Get the binders in the collection, in order.
+    pub fn binders(&self) -> &[Binder] {
+        &self.binders
+    }
+
+    /// Get the number of binders in the collection.
+    pub fn len(&self) -> u32 {
+        self.binders.len() as u32
+    }
+
+    /// Check if the collection has no binders.
+    pub fn is_empty(&self) -> bool {
+        self.binders.is_empty()
+    }
+
+    /// Get a binder by its 1-based number.
+    pub fn binder(&self, number: u32) -> Option<&Binder> {
+        self.binders.get(number.checked_sub(1)? as usize)
+    }
+
+    /// Get a mutable binder by its 1-based number.
+    pub fn binder_mut(&mut self, number: u32) -> Option<&mut Binder> {
+        self.binders.get_mut(number.checked_sub(1)? as usize)
+    }
+
+    /// Add a binder to the end of the collection.
+    pub fn push(&mut self, binder: Binder) {
+        self.binders.push(binder);
+    }
+
+    /// Remove a binder by its 1-based number.
+    ///
+    /// The last remaining binder cannot be removed.
+    pub fn remove(&mut self, number: u32) -> Option<Binder> {
+        if self.binders.len() <= 1 {
+            return None;
+        }
+
+        self.binder(number)?;
+        Some(self.binders.remove(number as usize - 1))
+    }
+
+    /// Get the number of slots across all binders.
+    pub fn capacity(&self) -> Result<u32, BinderError> {
+        total_capacity(&self.binders)
+    }
+
+    /// Get the index across the collection of the first card in a binder, by its 1-based number.
+    pub fn first_index(&self, number: u32) -> Result<SlotIndex, BinderError> {
+        if self.binder(number).is_none() {
+            return Err(BinderError::OutOfBounds {
+                dimension: Dimension::Binders,
+                position: number,
+                max: self.len(),
+            });
+        }
+
+        total_capacity(&self.binders[..number as usize - 1]).map(SlotIndex::new)
+    }
+
+    /// For a given index, return the card slot it falls in, overflowing into later binders.
+    pub fn locate_index(&self, index: SlotIndex) -> Result<CollectionSlot, BinderError> {
+        let mut remaining = index.get();
+        for (number, binder) in (1..).zip(&self.binders) {
+            let capacity = binder.capacity()?;
+            if remaining < capacity {
+                return Ok(CollectionSlot {
+                    binder: NonZeroU32::new(number).expect("Binder number should be non-zero"),
+                    slot: BinderSlot::from_card_number(
+                        binder,
+                        SlotIndex::new(remaining).to_card_number(),
+                    ),
+                    index,
+                });
+            }
+
+            remaining -= capacity;
+        }
+
+        Err(BinderError::BeyondCapacity {
+            index: index.get(),
+            capacity: self.capacity()?,
+        })
+    }
+
+    /// Get the 0-based card index to start a run of cards at, given the
+    /// number of cards already placed in the collection.
+    pub fn align(&self, card_index: u32, alignment: Alignment) -> Result<u32, BinderError> {
+        let mut start = 0u32;
+        for binder in &self.binders {
+            let capacity = binder.capacity()?;
+            if card_index - start < capacity {
+                let offset = binder.align(card_index - start, alignment)?;
+                return start.checked_add(offset).ok_or(BinderError::Overflow);
+            }
+
+            start = start.checked_add(capacity).ok_or(BinderError::Overflow)?;
+        }
+
+        Ok(card_index)
+    }
+
+    /// Plan inserting a card at an index, shifting the cards from there up to
+    /// (but not including) `end` forward by one pocket.
+    pub fn plan_insert(&self, at: SlotIndex, end: SlotIndex) -> Result<InsertPlan, BinderError> {
+        let slot = self.locate_index(at)?;
+        let shifts = (at.get()..end.get())
+            .rev()
+            .map(|index| {
+                let from = self.locate_index(SlotIndex::new(index))?;
+                let to = index
+                    .checked_add(1)
+                    .and_then(|next| self.locate_index(SlotIndex::new(next)).ok());
+                Ok(Shift { from, to })
+            })
+            .collect::<Result<_, BinderError>>()?;
+
+        Ok(InsertPlan { at: slot, shifts })
+    }
+
+    /// For a given card number, return the card slot it falls in.
+    pub fn locate(&self, card_number: CardNumber) -> Result<CollectionSlot, BinderError> {
+        self.locate_index(card_number.to_index())
+    }
+
+    /// For a given 1-based binder, page, row and column, return the corresponding card slot.
+    pub fn from_position(
+        &self,
+        binder: u32,
+        page: u32,
+        row: u32,
+        col: u32,
+    ) -> Result<CollectionSlot, BinderError> {
+        let Some(layout) = self.binder(binder) else {
+            return Err(BinderError::OutOfBounds {
+                dimension: Dimension::Binders,
+                position: binder,
+                max: self.len(),
+            });
+        };
+
+        let slot = BinderSlot::from_position(layout, page, row, col)?;
+        let card_number =
+            slot.card_number(layout)
+                .ok_or(BinderError::Reserved { page, row, col })?;
+        let index = self
+            .first_index(binder)?
+            .get()
+            .checked_add(card_number.to_index().get())
+            .ok_or(BinderError::Overflow)?;
+
+        Ok(CollectionSlot {
+            binder: NonZeroU32::new(binder).expect("Binder number should be non-zero"),
+            slot,
+            index: SlotIndex::new(index),
+        })
+    }
+}
+
+impl CollectionSlot {
+    /// Get the 1-based number of the binder the card slot is in.
+    pub fn binder(&self) -> u32 {
+        self.binder.get()
+    }
+
+    /// Get the card slot within its binder.
+    pub fn slot(&self) -> BinderSlot {
+        self.slot
+    }
+
+    /// Get the index of the card across the whole collection.
+    pub fn index(&self) -> SlotIndex {
+        self.index
+    }
+
+    /// Get the card number that belongs in the card slot.
+    pub fn card_number(&self) -> CardNumber {
+        self.index.to_card_number()
+    }
+}
+
+impl Shift {
+    /// Get the card slot the card is in now.
+    pub fn from(&self) -> CollectionSlot {
+        self.from
+    }
+
+    /// Get the card slot the card moves to, or `None` if it is pushed past the last page.
+    pub fn to(&self) -> Option<CollectionSlot> {
+        self.to
+    }
+}
+
+impl InsertPlan {
+    /// Get the card slot the inserted card goes in.
+    pub fn at(&self) -> CollectionSlot {
+        self.at
+    }
+
+    /// Get the cards to shift, last card first, so each moves into an empty pocket.
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shifts
+    }
+
+    /// Get the cards pushed past the last page of the last binder.
+    pub fn overflow(&self) -> impl Iterator<Item = CollectionSlot> + '_ {
+        self.shifts
+            .iter()
+            .filter(|shift| shift.to.is_none())
+            .map(|shift| shift.from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binder::Pocket;
+
+    fn collection() -> Collection {
+        let mut collection = Collection::new(Binder::new(3, 3, 2));
+        collection.push(Binder::new(2, 2, 10));
+        collection
+    }
+
+    #[test]
+    fn test_capacity() {
+        assert_eq!(collection().capacity(), Ok(58));
+    }
+
+    #[test]
+    fn test_overflow_into_next_binder() {
+        let collection = collection();
+
+        let location = collection.locate_index(SlotIndex::new(17)).unwrap();
+        assert_eq!(location.binder(), 1);
+        assert_eq!(location.slot().page(), 2);
+
+        let location = collection.locate_index(SlotIndex::new(18)).unwrap();
+        assert_eq!(location.binder(), 2);
+        assert_eq!(location.slot().page(), 1);
+        assert_eq!(location.slot().index().get(), 0);
+
+        let location = collection.locate_index(SlotIndex::new(31)).unwrap();
+        assert_eq!(location.binder(), 2);
+        assert_eq!(location.slot().page(), 4);
+        assert_eq!(location.slot().row(), 1);
+        assert_eq!(location.slot().col(), 2);
+
+        assert_eq!(
+            collection.locate_index(SlotIndex::new(58)),
+            Err(BinderError::BeyondCapacity {
+                index: 58,
+                capacity: 58
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_position() {
+        let collection = collection();
+        let location = collection.from_position(2, 4, 1, 2).unwrap();
+        assert_eq!(location.index().get(), 31);
+        assert_eq!(location, collection.locate_index(location.index()).unwrap());
+
+        assert!(collection.from_position(3, 1, 1, 1).is_err());
+        assert_eq!(collection.first_index(2), Ok(SlotIndex::new(18)));
+    }
+
+    #[test]
+    fn test_reserved_pockets() {
+        let mut collection = collection();
+        collection
+            .binder_mut(2)
+            .unwrap()
+            .reserve(Pocket::new(1, 1, 1))
+            .unwrap();
+
+        assert_eq!(collection.capacity(), Ok(57));
+        let location = collection.locate_index(SlotIndex::new(18)).unwrap();
+        assert_eq!(location.binder(), 2);
+        assert_eq!(location.slot().pocket(), Pocket::new(1, 1, 2));
+        assert_eq!(
+            collection.from_position(2, 1, 1, 2).unwrap().index().get(),
+            18
+        );
+        assert_eq!(
+            collection.from_position(2, 1, 1, 1),
+            Err(BinderError::Reserved {
+                page: 1,
+                row: 1,
+                col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        let collection = collection();
+        assert_eq!(collection.align(3, Alignment::NewPage), Ok(9));
+        assert_eq!(collection.align(10, Alignment::NewPage), Ok(18));
+        assert_eq!(collection.align(3, Alignment::NewBinder), Ok(18));
+        assert_eq!(collection.align(19, Alignment::NewPage), Ok(22));
+        assert_eq!(collection.align(60, Alignment::NewPage), Ok(60));
+    }
+
+    #[test]
+    fn test_plan_insert() {
+        let collection = collection();
+        let plan = collection
+            .plan_insert(SlotIndex::new(16), SlotIndex::new(20))
+            .unwrap();
+        assert_eq!(plan.at().slot().pocket(), Pocket::new(2, 3, 2));
+
+        let shifts = plan.shifts();
+        assert_eq!(shifts.len(), 4);
+        assert_eq!(shifts[0].from().index().get(), 19);
+        assert_eq!(shifts[0].to().unwrap().index().get(), 20);
+
+        // The last card of the first binder moves into the second
+        assert_eq!(shifts[2].from().binder(), 1);
+        assert_eq!(shifts[2].to().unwrap().binder(), 2);
+        assert_eq!(plan.overflow().count(), 0);
+
+        let plan = collection
+            .plan_insert(SlotIndex::new(50), SlotIndex::new(58))
+            .unwrap();
+        let overflow: Vec<u32> = plan.overflow().map(|slot| slot.index().get()).collect();
+        assert_eq!(overflow, [57]);
+    }
+
+    #[test]
+    fn test_remove_last_binder() {
+        let mut collection = collection();
+        assert!(collection.remove(3).is_none());
+        assert!(collection.remove(1).is_some());
+        assert!(collection.remove(1).is_none());
+        assert_eq!(collection.len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_without_binders() {
+        let json = serde_json::to_string(&collection()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Collection>(&json).unwrap(),
+            collection()
+        );
+        assert!(serde_json::from_str::<Collection>(r#"{"binders":[]}"#).is_err());
+    }
+}
@@ -1,7 +1,50 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+#[cfg(feature = "gui")]
 mod app;
+#[cfg(feature = "gui")]
 pub use app::App;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+#[cfg(not(target_arch = "wasm32"))]
+pub use cli::{BinderArgs, Cli, Command};
+
 mod binder;
+pub use binder::{
+    Alignment, Binder, BinderError, BinderSlot, Dimension, FillOrder, PageLayout, PageSide, Pocket,
+    Sidedness,
+};
+
+mod card_id;
+pub use card_id::{CardId, CardIdError, CardIdScheme, IdFormat, Subset};
+
 mod card_number;
+pub use card_number::{CardNumber, SlotIndex};
+
+mod checklist;
+pub use checklist::{Checklist, ChecklistError, ChecklistFormat, ChecklistPage, ChecklistPocket};
+
+mod collection;
+pub use collection::{Collection, CollectionSlot, InsertPlan, Shift};
+
+mod metadata;
+pub use metadata::{CardInfo, SetList, SetListError, SetListFormat};
+
+mod ownership;
+pub use ownership::{CardRecord, Ownership};
+
+mod reorganisation;
+pub use reorganisation::{Move, Position, Reorganisation};
+
+mod search;
+pub use search::{search, SearchMatch};
+
+mod set_definition;
+pub use set_definition::SetDefinition;
+
+mod set_layout;
+pub use set_layout::{PlacedSet, SetLayout};
+
+mod sort_plan;
+pub use sort_plan::{split_card_list, PlannedCard, SortPlan, SpreadGroup};
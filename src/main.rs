@@ -1,11 +1,84 @@
 #![warn(clippy::all, rust_2018_idioms)]
-#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
+// hide console window on Windows in release, unless there is no GUI to show
+#![cfg_attr(
+    all(not(debug_assertions), feature = "gui"),
+    windows_subsystem = "windows"
+)]
 
-// When compiling natively:
+/// Run the subcommand given on the command line, if any.
+///
+/// Returns `false` when no subcommand was given.
 #[cfg(not(target_arch = "wasm32"))]
+fn run_cli() -> bool {
+    use clap::Parser as _;
+
+    // Arguments mean a subcommand, help or a usage error, which all need somewhere to print
+    if std::env::args_os().len() > 1 {
+        attach_console();
+    }
+
+    let cli = tcg_bound::Cli::parse();
+    let Some(command) = cli.command else {
+        return false;
+    };
+
+    if let Err(e) = command.run(&mut std::io::stdout().lock()) {
+        eprintln!("Error: {e:#}");
+        std::process::exit(1);
+    }
+
+    true
+}
+
+/// Print to the console the app was started from, as Windows release builds of the GUI have none.
+#[cfg(all(windows, not(debug_assertions), feature = "gui"))]
+fn attach_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // SAFETY: AttachConsole takes a process ID by value and touches no memory of ours.
+    // It fails when there is no parent console, such as when started from Explorer,
+    // which leaves output going nowhere as before, so the result is not checked.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Other builds always have a console to print to.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(all(windows, not(debug_assertions), feature = "gui"))
+))]
+fn attach_console() {}
+
+// When compiling natively without the GUI:
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "gui")))]
+fn main() {
+    use clap::CommandFactory as _;
+
+    env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+
+    if !run_cli() {
+        tcg_bound::Cli::command()
+            .print_help()
+            .expect("Failed to print help");
+    }
+}
+
+// When compiling natively:
+#[cfg(all(not(target_arch = "wasm32"), feature = "gui"))]
 fn main() -> eframe::Result {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // Run headless when a subcommand is given, otherwise launch the GUI.
+    if run_cli() {
+        return Ok(());
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
@@ -25,7 +98,7 @@ fn main() -> eframe::Result {
 }
 
 // When compiling to web using trunk:
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "gui"))]
 fn main() {
     use eframe::wasm_bindgen::JsCast as _;
 
@@ -70,3 +143,7 @@ fn main() {
         }
     });
 }
+
+// The web build has nothing to run without the GUI.
+#[cfg(all(target_arch = "wasm32", not(feature = "gui")))]
+fn main() {}
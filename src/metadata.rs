@@ -0,0 +1,263 @@
+use std::{fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{binder::BinderError, card_id::CardIdError};
+
+/// What is known about a card from a set list.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct CardInfo {
+    name: String,
+    #[serde(default)]
+    rarity: Option<String>,
+    #[serde(default)]
+    card_type: Option<String>,
+    /// The variants the card is printed in, such as "Reverse holo".
+    #[serde(default)]
+    variants: Vec<String>,
+}
+
+/// The file formats a set list can be read from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SetListFormat {
+    /// An array of objects with `number`, `name`, `rarity`, `type` and `variants` fields.
+    Json,
+    /// A table with a header row naming the same columns as the JSON fields.
+    /// Variants are separated by semicolons.
+    Csv,
+}
+
+/// Errors that can occur when reading a set list or attaching it to a set.
+#[derive(Debug, Error)]
+pub enum SetListError {
+    /// The file extension is not one of the supported formats.
+    #[error("Unknown set list format {0:?}, expected .json or .csv")]
+    UnknownFormat(String),
+    /// The file could not be read.
+    #[error("Cannot read set list: {0}")]
+    Io(#[from] std::io::Error),
+    /// The JSON is malformed or missing fields.
+    #[error("Invalid JSON set list: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The CSV is malformed or missing columns.
+    #[error("Invalid CSV set list: {0}")]
+    Csv(#[from] csv::Error),
+    /// A card number in the set list is not a card in the set.
+    #[error("Card {id:?} is not in the set: {source}")]
+    InvalidCard { id: String, source: CardIdError },
+    /// A card in the set list has no pocket in the binders.
+    #[error("Card {id:?} does not fit in the binders: {source}")]
+    DoesNotFit { id: String, source: BinderError },
+}
+
+/// A card number in a set list, which may be written as a number or a card ID.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CardIdField {
+    Number(u32),
+    Id(String),
+}
+
+/// The variants of a card, as a JSON array or a semicolon separated CSV field.
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum VariantsField {
+    #[default]
+    None,
+    List(Vec<String>),
+    Text(String),
+}
+
+/// A row of a set list, as written in the file.
+#[derive(Debug, Deserialize)]
+struct SetListRow {
+    number: CardIdField,
+    name: String,
+    #[serde(default)]
+    rarity: Option<String>,
+    #[serde(default, rename = "type")]
+    card_type: Option<String>,
+    #[serde(default)]
+    variants: VariantsField,
+}
+
+/// A list of cards and what is known about them, keyed by card ID.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SetList {
+    cards: Vec<(String, CardInfo)>,
+}
+
+impl CardInfo {
+    /// Create a new card with only a name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.trim().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the name of the card.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the rarity of the card, if known.
+    pub fn rarity(&self) -> Option<&str> {
+        self.rarity.as_deref()
+    }
+
+    /// Get the type of the card, if known.
+    pub fn card_type(&self) -> Option<&str> {
+        self.card_type.as_deref()
+    }
+
+    /// Get the variants the card is printed in.
+    pub fn variants(&self) -> &[String] {
+        &self.variants
+    }
+}
+
+impl fmt::Display for CardInfo {
+    /// Write the card name with its rarity, such as "Pikachu (Common)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rarity {
+            Some(rarity) => write!(f, "{} ({rarity})", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl SetListFormat {
+    /// Guess the format of a set list from its file extension.
+    pub fn from_path(path: &Path) -> Result<Self, SetListError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(SetListError::UnknownFormat(extension.to_owned())),
+        }
+    }
+}
+
+/// Trim a text field, treating an empty field as missing.
+fn non_empty(field: Option<String>) -> Option<String> {
+    field
+        .map(|field| field.trim().to_owned())
+        .filter(|field| !field.is_empty())
+}
+
+impl SetListRow {
+    /// Split the row into its card ID and what is known about the card.
+    fn into_card(self) -> (String, CardInfo) {
+        let id = match self.number {
+            CardIdField::Number(number) => number.to_string(),
+            CardIdField::Id(id) => id.trim().to_owned(),
+        };
+
+        let variants = match self.variants {
+            VariantsField::None => Vec::new(),
+            VariantsField::List(variants) => variants,
+            VariantsField::Text(text) => text.split(';').map(str::to_owned).collect(),
+        };
+
+        let info = CardInfo {
+            name: self.name.trim().to_owned(),
+            rarity: non_empty(self.rarity),
+            card_type: non_empty(self.card_type),
+            variants: variants
+                .into_iter()
+                .filter_map(|variant| non_empty(Some(variant)))
+                .collect(),
+        };
+
+        (id, info)
+    }
+}
+
+impl SetList {
+    /// Parse a set list from the contents of a file.
+    pub fn parse(s: &str, format: SetListFormat) -> Result<Self, SetListError> {
+        let rows: Vec<SetListRow> = match format {
+            SetListFormat::Json => serde_json::from_str(s)?,
+            SetListFormat::Csv => csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(s.as_bytes())
+                .deserialize()
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            cards: rows.into_iter().map(SetListRow::into_card).collect(),
+        })
+    }
+
+    /// Read a set list from a `.json` or `.csv` file.
+    pub fn read(path: &Path) -> Result<Self, SetListError> {
+        let format = SetListFormat::from_path(path)?;
+        Self::parse(&std::fs::read_to_string(path)?, format)
+    }
+
+    /// Get the card IDs and what is known about each card, in file order.
+    pub fn cards(&self) -> &[(String, CardInfo)] {
+        &self.cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let list = SetList::parse(
+            r#"[
+                {"number": 25, "name": "Pikachu", "rarity": "Common", "type": "Lightning"},
+                {"number": "TG05", "name": "Charizard", "variants": ["Holo"]}
+            ]"#,
+            SetListFormat::Json,
+        )
+        .unwrap();
+
+        let (id, info) = &list.cards()[0];
+        assert_eq!(id, "25");
+        assert_eq!(info.to_string(), "Pikachu (Common)");
+        assert_eq!(info.card_type(), Some("Lightning"));
+
+        let (id, info) = &list.cards()[1];
+        assert_eq!(id, "TG05");
+        assert_eq!(info.to_string(), "Charizard");
+        assert_eq!(info.variants(), ["Holo"]);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let list = SetList::parse(
+            "number,name,rarity,type,variants\n\
+             025, Pikachu ,Common,Lightning,Holo; Reverse holo\n\
+             026,Raichu,,,\n",
+            SetListFormat::Csv,
+        )
+        .unwrap();
+
+        // Plain numbers are read as numbers, so leading zeros are dropped
+        let (id, info) = &list.cards()[0];
+        assert_eq!(id, "25");
+        assert_eq!(info.name(), "Pikachu");
+        assert_eq!(info.variants(), ["Holo", "Reverse holo"]);
+
+        let (_, info) = &list.cards()[1];
+        assert_eq!(info.rarity(), None);
+        assert!(info.variants().is_empty());
+
+        assert!(SetList::parse("number\n1\n", SetListFormat::Csv).is_err());
+        assert!(matches!(
+            SetListFormat::from_path(Path::new("cards.txt")),
+            Err(SetListError::UnknownFormat(_))
+        ));
+    }
+}
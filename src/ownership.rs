@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card_number::CardNumber;
+
+/// How many copies of a card are owned, including any variants such as reverse holos.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct CardRecord {
+    /// The number of copies of the standard card.
+    count: u32,
+    /// The number of copies of each named variant.
+    #[serde(default)]
+    variants: BTreeMap<String, u32>,
+}
+
+/// The cards owned from a set, keyed by card number.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct Ownership {
+    cards: BTreeMap<u32, CardRecord>,
+}
+
+impl CardRecord {
+    /// Get the number of copies of the standard card.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Get the number of copies of each named variant.
+    pub fn variants(&self) -> &BTreeMap<String, u32> {
+        &self.variants
+    }
+
+    /// Check if no copies of the card or its variants are owned.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0 && self.variants.is_empty()
+    }
+}
+
+impl Ownership {
+    /// Get the ownership record of a card, if any copies are owned.
+    pub fn record(&self, card: CardNumber) -> Option<&CardRecord> {
+        self.cards.get(&card.get())
+    }
+
+    /// Get the number of copies owned of the standard card.
+    pub fn count(&self, card: CardNumber) -> u32 {
+        self.record(card).map_or(0, CardRecord::count)
+    }
+
+    /// Check if at least one copy of the standard card is owned.
+    pub fn is_owned(&self, card: CardNumber) -> bool {
+        self.count(card) > 0
+    }
+
+    /// Update the number of copies owned of the standard card.
+    pub fn set_count(&mut self, card: CardNumber, count: u32) {
+        self.update(card, |record| record.count = count);
+    }
+
+    /// Update the number of copies owned of a named variant of a card.
+    pub fn set_variant_count(&mut self, card: CardNumber, variant: &str, count: u32) {
+        self.update(card, |record| {
+            if count == 0 {
+                record.variants.remove(variant);
+            } else {
+                record.variants.insert(variant.to_owned(), count);
+            }
+        });
+    }
+
+    /// Mark a missing card as owned, or an owned card as missing.
+    pub fn toggle(&mut self, card: CardNumber) {
+        let count = if self.is_owned(card) { 0 } else { 1 };
+        self.set_count(card, count);
+    }
+
+    /// Get the number of distinct cards with at least one standard copy owned.
+    pub fn owned_count(&self) -> u32 {
+        self.cards
+            .values()
+            .filter(|record| record.count > 0)
+            .count() as u32
+    }
+
+    /// Get the owned cards and their records, in card number order.
+    pub fn iter(&self) -> impl Iterator<Item = (CardNumber, &CardRecord)> {
+        self.cards
+            .iter()
+            .filter_map(|(&card, record)| Some((CardNumber::try_new(card, u32::MAX)?, record)))
+    }
+
+    /// Apply an update to a card's record, dropping the record once nothing is owned.
+    fn update(&mut self, card: CardNumber, f: impl FnOnce(&mut CardRecord)) {
+        let record = self.cards.entry(card.get()).or_default();
+        f(record);
+        if record.is_empty() {
+            self.cards.remove(&card.get());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ownership() {
+        let card = |n| CardNumber::try_new(n, u32::MAX).unwrap();
+        let mut ownership = Ownership::default();
+        assert!(!ownership.is_owned(card(5)));
+
+        ownership.toggle(card(5));
+        assert!(ownership.is_owned(card(5)));
+        assert_eq!(ownership.count(card(5)), 1);
+
+        ownership.set_count(card(7), 3);
+        ownership.set_variant_count(card(7), "Reverse holo", 1);
+        assert_eq!(ownership.owned_count(), 2);
+        assert_eq!(
+            ownership
+                .record(card(7))
+                .unwrap()
+                .variants()
+                .get("Reverse holo"),
+            Some(&1)
+        );
+
+        ownership.toggle(card(7));
+        assert!(!ownership.is_owned(card(7)));
+        assert!(ownership.record(card(7)).is_some());
+
+        ownership.set_variant_count(card(7), "Reverse holo", 0);
+        ownership.toggle(card(5));
+        assert!(ownership.record(card(7)).is_none());
+        assert_eq!(ownership.iter().count(), 0);
+    }
+}
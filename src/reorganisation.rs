@@ -0,0 +1,485 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::{
+    binder::Pocket,
+    card_number::CardNumber,
+    collection::{Collection, CollectionSlot},
+    set_layout::SetLayout,
+};
+
+/// A pocket a card moves from or to, numbered within the collection it belongs to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Position {
+    /// The 1-based number of the binder the pocket is in.
+    binder: u32,
+    pocket: Pocket,
+    /// The 1-based position of the pocket on its page.
+    slot_on_page: u32,
+}
+
+/// A step in moving cards to their pockets in a new collection layout.
+///
+/// Cards are given by the 0-based index of their set and their card number within it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Move {
+    /// Move a card into an empty pocket.
+    Move {
+        set: usize,
+        card: CardNumber,
+        from: Position,
+        to: Position,
+    },
+    /// Take a card out for now, to free its pocket when cards move in a loop.
+    SetAside {
+        set: usize,
+        card: CardNumber,
+        from: Position,
+    },
+    /// Put a card that was set aside into its new pocket.
+    PutBack {
+        set: usize,
+        card: CardNumber,
+        to: Position,
+    },
+    /// Take a card out for good, as it no longer fits in the collection.
+    Remove {
+        set: usize,
+        card: CardNumber,
+        from: Position,
+    },
+}
+
+/// The moves needed to go from one collection layout to another.
+///
+/// Cards only ever move into empty pockets. Cards that swap pockets in a loop
+/// are broken up by setting one card aside, so at most one card is held at a time.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Reorganisation {
+    moves: Vec<Move>,
+}
+
+/// A set index and card number, identifying a card across every set.
+type CardKey = (usize, CardNumber);
+
+/// A binder number and a pocket in it, identifying a pocket across the collection.
+type PocketKey = (u32, Pocket);
+
+impl Position {
+    fn new(collection: &Collection, slot: CollectionSlot) -> Self {
+        let binder = collection
+            .binder(slot.binder())
+            .expect("Located binder should exist");
+        Self {
+            binder: slot.binder(),
+            pocket: slot.slot().pocket(),
+            slot_on_page: slot.slot().slot_on_page(binder),
+        }
+    }
+
+    /// Get the 1-based number of the binder the pocket is in.
+    pub fn binder(&self) -> u32 {
+        self.binder
+    }
+
+    /// Get the pocket.
+    pub fn pocket(&self) -> Pocket {
+        self.pocket
+    }
+
+    /// Get the 1-based position of the pocket on its page.
+    pub fn slot_on_page(&self) -> u32 {
+        self.slot_on_page
+    }
+
+    fn key(&self) -> PocketKey {
+        (self.binder, self.pocket)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "b{} p{} s{}",
+            self.binder,
+            self.pocket.page(),
+            self.slot_on_page
+        )
+    }
+}
+
+impl Move {
+    /// Get the 0-based index of the set the card belongs to.
+    pub fn set(&self) -> usize {
+        match *self {
+            Move::Move { set, .. }
+            | Move::SetAside { set, .. }
+            | Move::PutBack { set, .. }
+            | Move::Remove { set, .. } => set,
+        }
+    }
+
+    /// Get the card being moved, numbered within its set.
+    pub fn card(&self) -> CardNumber {
+        match *self {
+            Move::Move { card, .. }
+            | Move::SetAside { card, .. }
+            | Move::PutBack { card, .. }
+            | Move::Remove { card, .. } => card,
+        }
+    }
+
+    /// Describe the move, referring to the card by a label such as "#14".
+    pub fn describe(&self, card: &str) -> String {
+        match self {
+            Move::Move { from, to, .. } => format!("Move {card} from {from} to {to}"),
+            Move::SetAside { from, .. } => format!("Set aside {card} from {from}"),
+            Move::PutBack { to, .. } => format!("Put {card} back in {to}"),
+            Move::Remove { from, .. } => format!("Remove {card} from {from}, it no longer fits"),
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(&format!("#{}", self.card().get())))
+    }
+}
+
+impl Reorganisation {
+    /// Plan the moves for the cards of every set when the collection changes
+    /// from `old` to `new`.
+    ///
+    /// Each card is located in both collections, so cards move between binders when
+    /// a binder's capacity changes, and follow their set when its aligned start moves.
+    /// Only owned cards are in the binders, so other pockets are free to move into.
+    /// When no card is marked as owned, every card is assumed to be in its pocket.
+    pub fn new(old: &Collection, new: &Collection, sets: &SetLayout) -> Self {
+        let tracks_ownership = sets
+            .sets()
+            .iter()
+            .any(|placed| placed.ownership().owned_count() > 0);
+
+        // The pocket each card is in, and the pocket it needs to go to, if any
+        let mut pending = BTreeMap::new();
+        let mut occupied = BTreeSet::new();
+        for (set, placed) in sets.sets().iter().enumerate() {
+            let owned = (1..=placed.set().card_count())
+                .filter_map(|n| CardNumber::try_new(n, u32::MAX))
+                .filter(|&card| !tracks_ownership || placed.ownership().is_owned(card));
+            for card in owned {
+                let Ok(from) = sets.locate(old, set, card) else {
+                    continue;
+                };
+
+                let from = Position::new(old, from);
+                occupied.insert(from.key());
+                let to = sets
+                    .locate(new, set, card)
+                    .ok()
+                    .map(|to| Position::new(new, to));
+                if to.map(|to| to.key()) != Some(from.key()) {
+                    pending.insert((set, card), (from, to));
+                }
+            }
+        }
+
+        // The card waiting to move into each pocket
+        let waiting = pending
+            .iter()
+            .filter_map(|(&card, (_, to))| Some((to.as_ref()?.key(), card)))
+            .collect();
+
+        let mut planner = Planner {
+            pending,
+            waiting,
+            occupied,
+            held: None,
+            moves: Vec::new(),
+        };
+
+        // Removing cards that no longer fit frees their pockets first
+        let removed: Vec<CardKey> = planner
+            .pending
+            .iter()
+            .filter(|(_, (_, to))| to.is_none())
+            .map(|(&card, _)| card)
+            .collect();
+        for (set, card) in removed {
+            let (from, _) = planner
+                .pending
+                .remove(&(set, card))
+                .expect("Card should be pending");
+            planner.moves.push(Move::Remove { set, card, from });
+            planner.free(from.key());
+        }
+
+        // Then every card whose new pocket is already empty, each freeing a pocket in turn
+        let cards: Vec<CardKey> = planner.pending.keys().copied().collect();
+        for &card in &cards {
+            let Some(&(_, Some(to))) = planner.pending.get(&card) else {
+                continue;
+            };
+
+            if !planner.occupied.contains(&to.key()) {
+                planner.waiting.remove(&to.key());
+                planner.move_card(card);
+            }
+        }
+
+        // Whatever is left moves in loops, so set one card of each loop aside to break it
+        for &(set, card) in &cards {
+            let Some((from, _)) = planner.pending.get(&(set, card)).copied() else {
+                continue;
+            };
+
+            planner.moves.push(Move::SetAside { set, card, from });
+            planner.occupied.remove(&from.key());
+            planner.held = Some((set, card));
+            planner.free(from.key());
+        }
+
+        Self {
+            moves: planner.moves,
+        }
+    }
+
+    /// Get the moves, in the order they should be made.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Check if no cards need to move.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+/// The state of the collection part way through planning a reorganisation.
+struct Planner {
+    /// The cards still to move, with the pocket they are in and the pocket they go to.
+    pending: BTreeMap<CardKey, (Position, Option<Position>)>,
+    /// The card waiting to move into each pocket.
+    waiting: BTreeMap<PocketKey, CardKey>,
+    /// The pockets holding a card.
+    occupied: BTreeSet<PocketKey>,
+    /// The card set aside to break a loop, if any.
+    held: Option<CardKey>,
+    moves: Vec<Move>,
+}
+
+impl Planner {
+    /// Move a pending card into its empty new pocket, then fill the pocket it leaves.
+    fn move_card(&mut self, (set, card): CardKey) {
+        let Some((from, Some(to))) = self.pending.remove(&(set, card)) else {
+            return;
+        };
+
+        self.moves.push(Move::Move {
+            set,
+            card,
+            from,
+            to,
+        });
+        self.occupied.remove(&from.key());
+        self.occupied.insert(to.key());
+        self.free(from.key());
+    }
+
+    /// Fill a pocket that has just been emptied, following the chain of cards it frees.
+    fn free(&mut self, mut pocket: PocketKey) {
+        while let Some((set, card)) = self.waiting.remove(&pocket) {
+            let Some((from, Some(to))) = self.pending.remove(&(set, card)) else {
+                return;
+            };
+
+            self.occupied.insert(to.key());
+
+            // The card was set aside, so it goes back in and the chain ends
+            if self.held == Some((set, card)) {
+                self.held = None;
+                self.moves.push(Move::PutBack { set, card, to });
+                return;
+            }
+
+            self.moves.push(Move::Move {
+                set,
+                card,
+                from,
+                to,
+            });
+            self.occupied.remove(&from.key());
+            pocket = from.key();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binder::{Alignment, Binder, FillOrder},
+        set_definition::SetDefinition,
+        set_layout::PlacedSet,
+    };
+
+    /// A set with every card owned.
+    fn owned_set(name: &str, count: u32, alignment: Alignment) -> PlacedSet {
+        let mut placed = PlacedSet::new(SetDefinition::new(name, count, count), alignment);
+        for n in 1..=count {
+            let card = CardNumber::try_new(n, u32::MAX).unwrap();
+            placed.ownership_mut().set_count(card, 1);
+        }
+        placed
+    }
+
+    /// Replay the moves, checking that cards only ever move into empty pockets
+    /// and end up where the sets place them in the new collection.
+    fn replay(old: &Collection, new: &Collection, sets: &SetLayout, moves: &[Move]) {
+        let owned = || {
+            sets.sets().iter().enumerate().flat_map(|(set, placed)| {
+                (1..=placed.set().card_count())
+                    .map(move |n| (set, CardNumber::try_new(n, u32::MAX).unwrap()))
+            })
+        };
+        let mut pockets: BTreeMap<PocketKey, CardKey> = owned()
+            .filter_map(|(set, card)| {
+                let slot = sets.locate(old, set, card).ok()?;
+                Some((Position::new(old, slot).key(), (set, card)))
+            })
+            .collect();
+        let mut held = None;
+
+        for step in moves {
+            let card = (step.set(), step.card());
+            match *step {
+                Move::Move { from, to, .. } => {
+                    assert_eq!(pockets.remove(&from.key()), Some(card));
+                    assert!(pockets.insert(to.key(), card).is_none(), "{step}");
+                }
+                Move::SetAside { from, .. } => {
+                    assert_eq!(pockets.remove(&from.key()), Some(card));
+                    assert!(held.replace(card).is_none());
+                }
+                Move::PutBack { to, .. } => {
+                    assert_eq!(held.take(), Some(card));
+                    assert!(pockets.insert(to.key(), card).is_none(), "{step}");
+                }
+                Move::Remove { from, .. } => {
+                    assert_eq!(pockets.remove(&from.key()), Some(card));
+                }
+            }
+        }
+
+        for (pocket, (set, card)) in pockets {
+            let slot = sets.locate(new, set, card).unwrap();
+            assert_eq!(Position::new(new, slot).key(), pocket);
+        }
+    }
+
+    #[test]
+    fn test_reorganisation() {
+        let old = Collection::new(Binder::new(3, 3, 2));
+        let sets = SetLayout::new(owned_set("First", 14, Alignment::Continue));
+        let mut new = old.clone();
+        new.binder_mut(1).unwrap().set_cols(4).unwrap();
+
+        let reorganisation = Reorganisation::new(&old, &new, &sets);
+        replay(&old, &new, &sets, reorganisation.moves());
+        assert_eq!(reorganisation.moves().len(), 11);
+        assert!(Reorganisation::new(&old, &old, &sets).is_empty());
+
+        // Without any ownership recorded, every card is assumed to be in its pocket
+        let mut untracked = SetLayout::new(PlacedSet::new(
+            SetDefinition::new("First", 14, 14),
+            Alignment::Continue,
+        ));
+        assert_eq!(Reorganisation::new(&old, &new, &untracked), reorganisation);
+
+        // Otherwise only the owned cards move
+        let first = CardNumber::try_new(1, u32::MAX).unwrap();
+        untracked
+            .set_mut(0)
+            .unwrap()
+            .ownership_mut()
+            .set_count(first, 1);
+        assert!(Reorganisation::new(&old, &new, &untracked).is_empty());
+
+        // Transposing a page moves cards in loops
+        let sets = SetLayout::new(owned_set("First", 18, Alignment::Continue));
+        let mut new = old.clone();
+        new.binder_mut(1)
+            .unwrap()
+            .set_fill_order(FillOrder::ColumnMajor);
+        let reorganisation = Reorganisation::new(&old, &new, &sets);
+        replay(&old, &new, &sets, reorganisation.moves());
+        assert!(reorganisation
+            .moves()
+            .iter()
+            .any(|step| matches!(step, Move::SetAside { .. })));
+
+        // Shrinking the only binder takes out the cards that no longer fit
+        let sets = SetLayout::new(owned_set("First", 12, Alignment::Continue));
+        let mut new = old.clone();
+        new.binder_mut(1).unwrap().set_pages(1).unwrap();
+        let reorganisation = Reorganisation::new(&old, &new, &sets);
+        assert_eq!(
+            reorganisation.moves()[0].to_string(),
+            "Remove #10 from b1 p2 s1, it no longer fits"
+        );
+        assert_eq!(reorganisation.moves().len(), 3);
+    }
+
+    #[test]
+    fn test_reorganisation_across_binders() {
+        let mut old = Collection::new(Binder::new(3, 3, 2));
+        old.push(Binder::new(3, 3, 2));
+
+        // Shrinking the first binder pushes cards into the second, which all move along
+        let sets = SetLayout::new(owned_set("First", 20, Alignment::Continue));
+        let mut new = old.clone();
+        new.binder_mut(1).unwrap().set_pages(1).unwrap();
+        let reorganisation = Reorganisation::new(&old, &new, &sets);
+        replay(&old, &new, &sets, reorganisation.moves());
+        assert!(!reorganisation
+            .moves()
+            .iter()
+            .any(|step| matches!(step, Move::Remove { .. })));
+        assert!(reorganisation.moves().iter().any(|step| matches!(
+            step,
+            Move::Move { from, to, .. } if from.binder() == 1 && to.binder() == 2
+        )));
+        assert!(reorganisation
+            .moves()
+            .iter()
+            .any(|step| matches!(step, Move::Move { card, .. } if card.get() == 20)));
+    }
+
+    #[test]
+    fn test_reorganisation_aligned_sets() {
+        let mut old = Collection::new(Binder::new(3, 3, 4));
+        old.push(Binder::new(3, 3, 4));
+
+        let mut sets = SetLayout::new(owned_set("First", 10, Alignment::Continue));
+        sets.push(owned_set("Second", 5, Alignment::NewPage));
+
+        // With four columns the first set fits on one page, so the second starts a page earlier
+        let mut new = old.clone();
+        new.binder_mut(1).unwrap().set_cols(4).unwrap();
+        assert_ne!(sets.starts(&old), sets.starts(&new));
+
+        let reorganisation = Reorganisation::new(&old, &new, &sets);
+        replay(&old, &new, &sets, reorganisation.moves());
+        let second = |step: &Move| step.set() == 1;
+        assert_eq!(
+            reorganisation.moves().iter().filter(|s| second(s)).count(),
+            5
+        );
+        assert!(reorganisation.moves().iter().filter(|s| second(s)).all(|step| {
+            matches!(step, Move::Move { from, to, .. } if from.pocket().page() == 3 && to.pocket().page() == 2)
+        }));
+    }
+}
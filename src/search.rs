@@ -0,0 +1,103 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::{card_number::CardNumber, set_layout::SetLayout};
+
+/// A card whose label or name matches a search.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SearchMatch {
+    /// The 0-based index of the set the card belongs to.
+    set: usize,
+    card: CardNumber,
+    /// How well the card matches, higher being better.
+    score: i64,
+}
+
+impl SearchMatch {
+    /// Get the 0-based index of the set the card belongs to.
+    pub fn set(&self) -> usize {
+        self.set
+    }
+
+    /// Get the card number within its set.
+    pub fn card(&self) -> CardNumber {
+        self.card
+    }
+
+    /// Get how well the card matches, higher being better.
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+}
+
+/// Fuzzy search the cards of every set by their label, such as "25/165" or "TG05",
+/// and their name from the set list, if any.
+///
+/// Matches are returned best first, then in set and card number order.
+pub fn search(sets: &SetLayout, query: &str) -> Vec<SearchMatch> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut matches = Vec::new();
+    for (set, placed) in sets.sets().iter().enumerate() {
+        let definition = placed.set();
+        for card in (1..=definition.card_count()).filter_map(|n| CardNumber::try_new(n, u32::MAX)) {
+            let text = match definition.card_name(card) {
+                Some(name) => format!("{} {name}", definition.label(card)),
+                None => definition.label(card),
+            };
+
+            if let Some(score) = matcher.fuzzy_match(&text, query) {
+                matches.push(SearchMatch { set, card, score });
+            }
+        }
+    }
+
+    matches.sort_by_key(|m| (std::cmp::Reverse(m.score), m.set, m.card));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binder::Alignment,
+        metadata::{SetList, SetListFormat},
+        set_definition::SetDefinition,
+        set_layout::PlacedSet,
+    };
+
+    #[test]
+    fn test_search() {
+        let mut first = SetDefinition::new("First", 30, 30);
+        let list = SetList::parse(
+            "number,name\n4,Charmander\n5,Charmeleon\n6,Charizard\n25,Pikachu\n",
+            SetListFormat::Csv,
+        )
+        .unwrap();
+        assert!(first.attach(&list).is_empty());
+
+        let mut sets = SetLayout::new(PlacedSet::new(first, Alignment::Continue));
+        sets.push(PlacedSet::new(
+            SetDefinition::new("Second", 10, 10),
+            Alignment::NewPage,
+        ));
+
+        let found = |query| -> Vec<(usize, u32)> {
+            search(&sets, query)
+                .into_iter()
+                .map(|m| (m.set(), m.card().get()))
+                .collect()
+        };
+
+        assert_eq!(found("pika"), [(0, 25)]);
+        assert_eq!(found("chzard"), [(0, 6)]);
+        assert_eq!(found("charm")[..2], [(0, 4), (0, 5)]);
+
+        // Numbers match the card labels of every set
+        assert!(found("7/10").contains(&(1, 7)));
+        assert!(found("  ").is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card_id::{CardId, CardIdError, CardIdScheme, Subset},
+    card_number::CardNumber,
+    metadata::{CardInfo, SetList, SetListError},
+};
+
+/// A card set, numbered like "198/165" where cards past the printed total are secret rares.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct SetDefinition {
+    /// The name of the set.
+    name: String,
+    /// The total printed on each card, excluding secret rares.
+    printed_total: u32,
+    /// The number of cards in the main set, including secret rares.
+    total: u32,
+    /// How card identifiers are written, including any subsets placed after the main set.
+    card_ids: CardIdScheme,
+    /// What is known about each card, keyed by card number.
+    #[serde(default)]
+    cards: BTreeMap<u32, CardInfo>,
+}
+
+impl Default for SetDefinition {
+    fn default() -> Self {
+        Self::new("", 100, 100)
+    }
+}
+
+impl SetDefinition {
+    /// Create a new set. The total is raised to at least the printed total.
+    pub fn new(name: &str, printed_total: u32, total: u32) -> Self {
+        let printed_total = printed_total.max(1);
+        Self {
+            name: name.to_owned(),
+            printed_total,
+            total: total.max(printed_total),
+            card_ids: CardIdScheme::default(),
+            cards: BTreeMap::new(),
+        }
+    }
+
+    /// Get the name of the set.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Update the name of the set.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    /// Get the total printed on each card, excluding secret rares.
+    pub fn printed_total(&self) -> u32 {
+        self.printed_total
+    }
+
+    /// Update the printed total, raising the total to match if needed.
+    pub fn set_printed_total(&mut self, printed_total: u32) {
+        self.printed_total = printed_total.max(1);
+        self.total = self.total.max(self.printed_total);
+    }
+
+    /// Get the number of cards in the main set, including secret rares.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Update the number of cards in the main set, lowering the printed total to match if needed.
+    pub fn set_total(&mut self, total: u32) {
+        self.total = total.max(1);
+        self.printed_total = self.printed_total.min(self.total);
+    }
+
+    /// Get the number of secret rares numbered past the printed total.
+    pub fn secret_count(&self) -> u32 {
+        self.total - self.printed_total
+    }
+
+    /// Get how card identifiers are written.
+    pub fn card_ids(&self) -> &CardIdScheme {
+        &self.card_ids
+    }
+
+    /// Get how card identifiers are written for editing.
+    pub fn card_ids_mut(&mut self) -> &mut CardIdScheme {
+        &mut self.card_ids
+    }
+
+    /// Get the subsets placed after the main set, in order.
+    pub fn subsets(&self) -> &[Subset] {
+        self.card_ids.subsets()
+    }
+
+    /// Get the number of card numbers the set needs, including secret rares and subsets.
+    pub fn card_count(&self) -> u32 {
+        self.card_ids.total(self.total)
+    }
+
+    /// Parse an identifier such as "198" or "TG05" and map it onto its card number.
+    pub fn parse(&self, s: &str) -> Result<CardNumber, CardIdError> {
+        self.card_ids.parse(s, self.total)
+    }
+
+    /// Get the identifier of a card number.
+    pub fn card_id(&self, card_number: CardNumber) -> Option<CardId> {
+        self.card_ids.card_id(card_number, self.total)
+    }
+
+    /// Check if a card number is a secret rare, numbered past the printed total.
+    pub fn is_secret(&self, card_number: CardNumber) -> bool {
+        self.card_ids.in_main_set(card_number, self.total)
+            && self
+                .card_id(card_number)
+                .is_some_and(|id| id.number() > self.printed_total)
+    }
+
+    /// Get what is known about a card, if anything.
+    pub fn card_info(&self, card_number: CardNumber) -> Option<&CardInfo> {
+        self.cards.get(&card_number.get())
+    }
+
+    /// Get the name of a card, if known.
+    pub fn card_name(&self, card_number: CardNumber) -> Option<&str> {
+        self.card_info(card_number).map(CardInfo::name)
+    }
+
+    /// Get what is known about each card, in card number order.
+    pub fn card_infos(&self) -> impl Iterator<Item = (CardNumber, &CardInfo)> {
+        self.cards
+            .iter()
+            .filter_map(|(&card, info)| Some((CardNumber::try_new(card, u32::MAX)?, info)))
+    }
+
+    /// Replace what is known about the cards of the set with a set list.
+    ///
+    /// Cards whose ID is not in the set are skipped and reported.
+    pub fn attach(&mut self, list: &SetList) -> Vec<SetListError> {
+        self.cards.clear();
+
+        let mut errors = Vec::new();
+        for (id, info) in list.cards() {
+            match self.parse(id) {
+                Ok(card_number) => {
+                    self.cards.insert(card_number.get(), info.clone());
+                }
+                Err(source) => errors.push(SetListError::InvalidCard {
+                    id: id.clone(),
+                    source,
+                }),
+            }
+        }
+
+        errors
+    }
+
+    /// Get the label printed on a card, such as "25/165", "198/165 (secret)" or "TG05".
+    pub fn label(&self, card_number: CardNumber) -> String {
+        let Some(id) = self.card_id(card_number) else {
+            return card_number.get().to_string();
+        };
+
+        if !self.card_ids.in_main_set(card_number, self.total) {
+            return id.to_string();
+        }
+
+        let digits = self.card_ids.main().digits() as usize;
+        let label = format!("{id}/{:0digits$}", self.printed_total);
+        if self.is_secret(card_number) {
+            format!("{label} (secret)")
+        } else {
+            label
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::SetListFormat;
+
+    #[test]
+    fn test_totals() {
+        let mut set = SetDefinition::new("Obsidian Flames", 197, 230);
+        assert_eq!(set.secret_count(), 33);
+
+        set.set_total(150);
+        assert_eq!((set.printed_total(), set.total()), (150, 150));
+
+        set.set_printed_total(165);
+        assert_eq!((set.printed_total(), set.total()), (165, 165));
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut set = SetDefinition::new("Scarlet & Violet", 165, 207);
+        set.card_ids_mut().main_mut().set_digits(3);
+        set.card_ids_mut().subsets_mut().push(Subset::new("TG", 30));
+        assert_eq!(set.card_count(), 237);
+
+        let card = |n| CardNumber::try_new(n, 237).unwrap();
+        assert_eq!(set.label(card(25)), "025/165");
+        assert!(!set.is_secret(card(165)));
+        assert_eq!(set.label(card(198)), "198/165 (secret)");
+        assert_eq!(set.label(card(212)), "TG5");
+        assert!(!set.is_secret(card(212)));
+
+        assert_eq!(set.parse("198"), Ok(card(198)));
+        assert_eq!(set.parse("TG05"), Ok(card(212)));
+    }
+
+    #[test]
+    fn test_attach() {
+        let mut set = SetDefinition::new("Scarlet & Violet", 165, 165);
+        let list = SetList::parse(
+            "number,name,rarity\n025,Pikachu,Common\n300,Mew,Rare\n",
+            SetListFormat::Csv,
+        )
+        .unwrap();
+
+        let errors = set.attach(&list);
+        let card = |n| CardNumber::try_new(n, 165).unwrap();
+        assert_eq!(
+            set.card_info(card(25)).unwrap().to_string(),
+            "Pikachu (Common)"
+        );
+        assert_eq!(set.card_name(card(26)), None);
+        assert!(matches!(
+            errors.as_slice(),
+            [SetListError::InvalidCard { id, .. }] if id == "300"
+        ));
+    }
+}
@@ -0,0 +1,435 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    binder::{Alignment, BinderError, Dimension},
+    card_number::{CardNumber, SlotIndex},
+    collection::{Collection, CollectionSlot},
+    metadata::{SetList, SetListError},
+    ownership::Ownership,
+    set_definition::SetDefinition,
+};
+
+/// A set placed in a collection, starting where its alignment puts it.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct PlacedSet {
+    set: SetDefinition,
+    #[serde(default)]
+    alignment: Alignment,
+    /// The cards owned from the set.
+    #[serde(default)]
+    ownership: Ownership,
+}
+
+/// An ordered list of sets laid out one after another across a collection.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(try_from = "SetLayoutData")]
+pub struct SetLayout {
+    sets: Vec<PlacedSet>,
+}
+
+/// A set layout as it is stored, checked to hold a set before it is used.
+#[derive(Deserialize)]
+struct SetLayoutData {
+    sets: Vec<PlacedSet>,
+}
+
+impl Default for SetLayout {
+    fn default() -> Self {
+        Self::new(PlacedSet::default())
+    }
+}
+
+impl PlacedSet {
+    /// Create a new placed set.
+    pub fn new(set: SetDefinition, alignment: Alignment) -> Self {
+        Self {
+            set,
+            alignment,
+            ownership: Ownership::default(),
+        }
+    }
+
+    /// Get the set definition.
+    pub fn set(&self) -> &SetDefinition {
+        &self.set
+    }
+
+    /// Get the set definition for editing.
+    pub fn set_mut(&mut self) -> &mut SetDefinition {
+        &mut self.set
+    }
+
+    /// Get where the set starts relative to the set before it.
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Update where the set starts relative to the set before it.
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Get the cards owned from the set.
+    pub fn ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    /// Get the cards owned from the set for editing.
+    pub fn ownership_mut(&mut self) -> &mut Ownership {
+        &mut self.ownership
+    }
+}
+
+impl TryFrom<SetLayoutData> for SetLayout {
+    type Error = BinderError;
+
+    fn try_from(data: SetLayoutData) -> Result<Self, Self::Error> {
+        if data.sets.is_empty() {
+            return Err(BinderError::ZeroDimension(Dimension::Sets));
+        }
+
+        Ok(Self { sets: data.sets })
+    }
+}
+
+impl SetLayout {
+    /// Create a new layout holding a single set.
+    pub fn new(set: PlacedSet) -> Self {
+        Self { sets: vec![set] }
+    }
+
+    /// Get the sets in the layout, in order.
+    pub fn sets(&self) -> &[PlacedSet] {
+        &self.sets
+    }
+
+    /// Get the number of sets in the layout.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Check if the layout has no sets.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Get a set by its 0-based index.
+    pub fn set(&self, index: usize) -> Option<&PlacedSet> {
+        self.sets.get(index)
+    }
+
+    /// Get a mutable set by its 0-based index.
+    pub fn set_mut(&mut self, index: usize) -> Option<&mut PlacedSet> {
+        self.sets.get_mut(index)
+    }
+
+    /// Add a set after the last one.
+    pub fn push(&mut self, set: PlacedSet) {
+        self.sets.push(set);
+    }
+
+    /// Remove a set by its 0-based index.
+    ///
+    /// The last remaining set cannot be removed.
+    pub fn remove(&mut self, index: usize) -> Option<PlacedSet> {
+        if self.sets.len() <= 1 || index >= self.sets.len() {
+            return None;
+        }
+
+        Some(self.sets.remove(index))
+    }
+
+    /// Get the 0-based card index across the collection that each set starts at.
+    pub fn starts(&self, collection: &Collection) -> Result<Vec<u32>, BinderError> {
+        let mut starts = Vec::with_capacity(self.sets.len());
+        let mut next = 0u32;
+        for placed in &self.sets {
+            let start = collection.align(next, placed.alignment)?;
+            starts.push(start);
+            next = start
+                .checked_add(placed.set.card_count())
+                .ok_or(BinderError::Overflow)?;
+        }
+
+        Ok(starts)
+    }
+
+    /// Get the number of card slots the sets take up, including any skipped to align them.
+    pub fn end(&self, collection: &Collection) -> Result<u32, BinderError> {
+        let starts = self.starts(collection)?;
+        match (starts.last(), self.sets.last()) {
+            (Some(start), Some(placed)) => start
+                .checked_add(placed.set.card_count())
+                .ok_or(BinderError::Overflow),
+            _ => Ok(0),
+        }
+    }
+
+    /// For a given set and card number within it, return the card slot it falls in.
+    pub fn locate(
+        &self,
+        collection: &Collection,
+        set: usize,
+        card_number: CardNumber,
+    ) -> Result<CollectionSlot, BinderError> {
+        let Some(placed) = self.sets.get(set) else {
+            return Err(BinderError::OutOfBounds {
+                dimension: Dimension::Sets,
+                position: set as u32 + 1,
+                max: self.sets.len() as u32,
+            });
+        };
+
+        let card_count = placed.set.card_count();
+        if card_number.get() > card_count {
+            return Err(BinderError::OutOfBounds {
+                dimension: Dimension::Cards,
+                position: card_number.get(),
+                max: card_count,
+            });
+        }
+
+        let index = self.starts(collection)?[set]
+            .checked_add(card_number.to_index().get())
+            .ok_or(BinderError::Overflow)?;
+        collection.locate_index(SlotIndex::new(index))
+    }
+
+    /// For a given card index across the collection, return the 0-based set
+    /// and card number that belong there, if any.
+    pub fn card_at(
+        &self,
+        collection: &Collection,
+        index: SlotIndex,
+    ) -> Result<Option<(usize, CardNumber)>, BinderError> {
+        let starts = self.starts(collection)?;
+        for (set, (start, placed)) in starts.into_iter().zip(&self.sets).enumerate() {
+            let Some(offset) = index.get().checked_sub(start) else {
+                continue;
+            };
+
+            if offset < placed.set.card_count() {
+                return Ok(Some((set, SlotIndex::new(offset).to_card_number())));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the set after a set if it starts in the pocket right after the set's last card,
+    /// so inserting a card into the set pushes its last card into the next set.
+    pub fn insert_collision(
+        &self,
+        collection: &Collection,
+        set: usize,
+    ) -> Result<Option<usize>, BinderError> {
+        let starts = self.starts(collection)?;
+        let (Some(&start), Some(&next_start), Some(placed)) =
+            (starts.get(set), starts.get(set + 1), self.sets.get(set))
+        else {
+            return Ok(None);
+        };
+
+        let end = start
+            .checked_add(placed.set.card_count())
+            .ok_or(BinderError::Overflow)?;
+        Ok((end >= next_start).then_some(set + 1))
+    }
+
+    /// Replace what is known about the cards of a set with a set list.
+    ///
+    /// Cards whose ID is not in the set are skipped and reported. Cards that do not fit
+    /// in the collection are kept, so they show once the binders grow, but are reported too.
+    pub fn attach(
+        &mut self,
+        collection: &Collection,
+        set: usize,
+        list: &SetList,
+    ) -> Vec<SetListError> {
+        let Some(placed) = self.sets.get_mut(set) else {
+            return Vec::new();
+        };
+
+        let mut errors = placed.set.attach(list);
+        let definition = &self.sets[set].set;
+        for (id, _) in list.cards() {
+            let Ok(card) = definition.parse(id) else {
+                continue;
+            };
+
+            if let Err(source) = self.locate(collection, set, card) {
+                errors.push(SetListError::DoesNotFit {
+                    id: id.clone(),
+                    source,
+                });
+            }
+        }
+
+        errors
+    }
+
+    /// Get the cards of a set with a set list entry that do not fit in the collection,
+    /// with the reason why.
+    pub fn unplaced_cards(
+        &self,
+        collection: &Collection,
+        set: usize,
+    ) -> Vec<(CardNumber, BinderError)> {
+        let Some(placed) = self.sets.get(set) else {
+            return Vec::new();
+        };
+
+        placed
+            .set
+            .card_infos()
+            .filter_map(|(card, _)| Some((card, self.locate(collection, set, card).err()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binder::Binder,
+        metadata::{SetList, SetListFormat},
+    };
+
+    fn layout() -> SetLayout {
+        let mut layout = SetLayout::new(PlacedSet::new(
+            SetDefinition::new("First", 10, 10),
+            Alignment::Continue,
+        ));
+        layout.push(PlacedSet::new(
+            SetDefinition::new("Second", 5, 5),
+            Alignment::NewPage,
+        ));
+        layout.push(PlacedSet::new(
+            SetDefinition::new("Third", 4, 4),
+            Alignment::NewSpread,
+        ));
+        layout.push(PlacedSet::new(
+            SetDefinition::new("Fourth", 2, 2),
+            Alignment::Continue,
+        ));
+        layout
+    }
+
+    #[test]
+    fn test_starts() {
+        let collection = Collection::new(Binder::new(3, 3, 10));
+        let layout = layout();
+
+        // Page 1 is alone on the first spread, then pages 2 and 3, then 4 and 5
+        assert_eq!(layout.starts(&collection), Ok(vec![0, 18, 27, 31]));
+        assert_eq!(layout.end(&collection), Ok(33));
+    }
+
+    #[test]
+    fn test_locate() {
+        let collection = Collection::new(Binder::new(3, 3, 10));
+        let layout = layout();
+
+        let card = |n| CardNumber::try_new(n, u32::MAX).unwrap();
+        let location = layout.locate(&collection, 1, card(1)).unwrap();
+        assert_eq!(location.slot().page(), 3);
+        assert_eq!(location.slot().slot_on_page(&Binder::new(3, 3, 10)), 1);
+        assert_eq!(location.index().get(), 18);
+
+        let location = layout.locate(&collection, 2, card(4)).unwrap();
+        assert_eq!(location.slot().page(), 4);
+        assert_eq!(location.index().get(), 30);
+
+        assert_eq!(
+            layout.locate(&collection, 1, card(6)),
+            Err(BinderError::OutOfBounds {
+                dimension: Dimension::Cards,
+                position: 6,
+                max: 5
+            })
+        );
+        assert!(layout.locate(&collection, 4, card(1)).is_err());
+
+        assert_eq!(
+            layout.card_at(&collection, SlotIndex::new(31)),
+            Ok(Some((3, card(1))))
+        );
+        assert_eq!(layout.card_at(&collection, SlotIndex::new(12)), Ok(None));
+    }
+
+    #[test]
+    fn test_unplaced_cards() {
+        let collection = Collection::new(Binder::new(3, 3, 3));
+        let mut layout = layout();
+        let list = SetList::parse(
+            r#"[{"number": 1, "name": "Sprigatito"}, {"number": 4, "name": "Fuecoco"}]"#,
+            SetListFormat::Json,
+        )
+        .unwrap();
+        assert!(layout.attach(&collection, 0, &list).is_empty());
+
+        // Cards that do not fit are still attached, but reported
+        let errors = layout.attach(&collection, 2, &list);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "Card \"1\" does not fit in the binders: \
+             Position 28 is beyond the capacity of 27 cards"
+        );
+        assert!(layout
+            .set(2)
+            .unwrap()
+            .set()
+            .card_name(CardNumber::try_new(1, 4).unwrap())
+            .is_some());
+
+        // The third set starts on a new spread, after the last page of the binder
+        assert!(layout.unplaced_cards(&collection, 0).is_empty());
+        let unplaced: Vec<u32> = layout
+            .unplaced_cards(&collection, 2)
+            .into_iter()
+            .map(|(card, _)| card.get())
+            .collect();
+        assert_eq!(unplaced, [1, 4]);
+    }
+
+    #[test]
+    fn test_remove_last_set() {
+        let mut layout = layout();
+        assert!(layout.remove(4).is_none());
+        for _ in 0..3 {
+            assert!(layout.remove(0).is_some());
+        }
+        assert!(layout.remove(0).is_none());
+        assert_eq!(layout.len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_without_sets() {
+        let json = serde_json::to_string(&layout()).unwrap();
+        assert_eq!(serde_json::from_str::<SetLayout>(&json).unwrap(), layout());
+        assert!(serde_json::from_str::<SetLayout>(r#"{"sets":[]}"#).is_err());
+    }
+
+    #[test]
+    fn test_insert_collision() {
+        let collection = Collection::new(Binder::new(3, 3, 10));
+
+        // Sets that continue straight on leave no room to insert a card
+        let mut continued = SetLayout::new(PlacedSet::new(
+            SetDefinition::new("First", 5, 5),
+            Alignment::Continue,
+        ));
+        continued.push(PlacedSet::new(
+            SetDefinition::new("Second", 5, 5),
+            Alignment::Continue,
+        ));
+        assert_eq!(continued.insert_collision(&collection, 0), Ok(Some(1)));
+        assert_eq!(continued.insert_collision(&collection, 1), Ok(None));
+
+        // Aligned sets leave empty pockets to shift into
+        let layout = layout();
+        assert_eq!(layout.insert_collision(&collection, 0), Ok(None));
+        assert_eq!(layout.insert_collision(&collection, 2), Ok(Some(3)));
+    }
+}
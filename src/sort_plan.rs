@@ -0,0 +1,267 @@
+use std::fmt;
+
+use crate::{
+    binder::{BinderError, BinderSlot, PageSide},
+    card_number::CardNumber,
+    collection::{Collection, CollectionSlot},
+};
+
+/// A card to insert, and the pocket it goes in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PlannedCard {
+    card: CardNumber,
+    slot: BinderSlot,
+    /// The 1-based position of the pocket on its page.
+    slot_on_page: u32,
+}
+
+/// The cards to insert while one spread of a binder is open.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpreadGroup {
+    /// The 1-based number of the binder.
+    binder: u32,
+    /// The 0-based spread within the binder.
+    spread: u32,
+    /// The pages shown on the left and right of the spread, if any.
+    pages: (Option<u32>, Option<u32>),
+    /// The cards to insert, in page then pocket order.
+    cards: Vec<PlannedCard>,
+}
+
+/// A plan to insert a pile of cards in a single pass through the binders,
+/// grouping the cards by spread from front to back.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SortPlan {
+    groups: Vec<SpreadGroup>,
+    /// Cards that could not be located, with the reason why.
+    unplaced: Vec<(CardNumber, BinderError)>,
+}
+
+impl PlannedCard {
+    /// Get the card to insert.
+    pub fn card(&self) -> CardNumber {
+        self.card
+    }
+
+    /// Get the pocket the card goes in.
+    pub fn slot(&self) -> BinderSlot {
+        self.slot
+    }
+
+    /// Get the 1-based position of the pocket on its page.
+    pub fn slot_on_page(&self) -> u32 {
+        self.slot_on_page
+    }
+}
+
+impl SpreadGroup {
+    /// Get the 1-based number of the binder.
+    pub fn binder(&self) -> u32 {
+        self.binder
+    }
+
+    /// Get the 0-based spread within the binder.
+    pub fn spread(&self) -> u32 {
+        self.spread
+    }
+
+    /// Get the cards to insert, in page then pocket order.
+    pub fn cards(&self) -> &[PlannedCard] {
+        &self.cards
+    }
+
+    /// Describe the group, labelling each card with a function such as [`SetDefinition::label`].
+    ///
+    /// [`SetDefinition::label`]: crate::SetDefinition::label
+    pub fn describe(&self, label: impl Fn(CardNumber) -> String) -> String {
+        let mut description = String::new();
+        self.write(&mut description, &label)
+            .expect("Writing to a string should not fail");
+        description
+    }
+
+    fn write(&self, f: &mut impl fmt::Write, label: &dyn Fn(CardNumber) -> String) -> fmt::Result {
+        // Only name the page of each pocket when the spread shows two pages
+        let two_pages = match self.pages {
+            (Some(left), Some(right)) => {
+                write!(f, "Binder {}, pages {left}–{right}:", self.binder)?;
+                true
+            }
+            (Some(page), None) | (None, Some(page)) => {
+                write!(f, "Binder {}, page {page}:", self.binder)?;
+                false
+            }
+            (None, None) => {
+                write!(f, "Binder {}, spread {}:", self.binder, self.spread + 1)?;
+                false
+            }
+        };
+
+        for (i, page_cards) in self
+            .cards
+            .chunk_by(|a, b| a.slot.page() == b.slot.page())
+            .enumerate()
+        {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            if two_pages {
+                write!(f, " page {}", page_cards[0].slot.page())?;
+            }
+
+            for (j, planned) in page_cards.iter().enumerate() {
+                let separator = if j == 0 { "" } else { "," };
+                write!(
+                    f,
+                    "{separator} slot {} (#{})",
+                    planned.slot_on_page,
+                    label(planned.card)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SpreadGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &|card| card.get().to_string())
+    }
+}
+
+impl SortPlan {
+    /// Plan where to insert a pile of cards, numbered across the whole collection.
+    pub fn new(collection: &Collection, cards: impl IntoIterator<Item = CardNumber>) -> Self {
+        Self::from_locations(
+            collection,
+            cards
+                .into_iter()
+                .map(|card| (card, collection.locate(card))),
+        )
+    }
+
+    /// Plan where to insert a pile of cards that have already been located.
+    ///
+    /// The card numbers are only used to label the plan, so they may be numbered within a set.
+    pub fn from_locations(
+        collection: &Collection,
+        locations: impl IntoIterator<Item = (CardNumber, Result<CollectionSlot, BinderError>)>,
+    ) -> Self {
+        let mut located = Vec::new();
+        let mut unplaced = Vec::new();
+        for (card, location) in locations {
+            match location {
+                Ok(location) => located.push((card, location)),
+                Err(e) => unplaced.push((card, e)),
+            }
+        }
+
+        // Front to back through the binders, then in page and pocket order
+        located.sort_by_key(|(_, location)| (location.binder(), location.slot().index()));
+
+        let mut groups: Vec<SpreadGroup> = Vec::new();
+        for (card, location) in located {
+            let Some(binder) = collection.binder(location.binder()) else {
+                continue;
+            };
+
+            let slot = location.slot();
+            let spread = binder.spread_of(slot.page());
+            let planned = PlannedCard {
+                card,
+                slot,
+                slot_on_page: slot.slot_on_page(binder),
+            };
+
+            match groups.last_mut() {
+                Some(group) if (group.binder, group.spread) == (location.binder(), spread) => {
+                    group.cards.push(planned);
+                }
+                _ => groups.push(SpreadGroup {
+                    binder: location.binder(),
+                    spread,
+                    pages: (
+                        binder.spread_page(spread, PageSide::Left),
+                        binder.spread_page(spread, PageSide::Right),
+                    ),
+                    cards: vec![planned],
+                }),
+            }
+        }
+
+        Self { groups, unplaced }
+    }
+
+    /// Get the spreads to open, front to back, with the cards to insert in each.
+    pub fn groups(&self) -> &[SpreadGroup] {
+        &self.groups
+    }
+
+    /// Get the cards that could not be located, with the reason why.
+    pub fn unplaced(&self) -> &[(CardNumber, BinderError)] {
+        &self.unplaced
+    }
+}
+
+/// Split a pasted list of cards on whitespace, commas and semicolons.
+pub fn split_card_list(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{binder::Binder, card_id::Subset, set_definition::SetDefinition};
+
+    #[test]
+    fn test_sort_plan() {
+        let collection = Collection::new(Binder::new(3, 3, 10));
+        let cards = [30, 2, 12, 40, 8, 100].map(|n| CardNumber::try_new(n, u32::MAX).unwrap());
+        let plan = SortPlan::new(&collection, cards);
+
+        // Page 1 is alone on the first spread, then pages 2 and 3, then 4 and 5
+        let lines: Vec<String> = plan.groups().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "Binder 1, page 1: slot 2 (#2), slot 8 (#8)",
+                "Binder 1, pages 2–3: page 2 slot 3 (#12)",
+                "Binder 1, pages 4–5: page 4 slot 3 (#30); page 5 slot 4 (#40)",
+            ]
+        );
+        assert_eq!(
+            plan.unplaced(),
+            [(
+                CardNumber::try_new(100, u32::MAX).unwrap(),
+                BinderError::BeyondCapacity {
+                    index: 99,
+                    capacity: 90
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let collection = Collection::new(Binder::new(3, 3, 10));
+        let mut set = SetDefinition::new("Scarlet & Violet", 9, 9);
+        let mut subset = Subset::new("TG", 30);
+        subset.format_mut().set_digits(2);
+        set.card_ids_mut().subsets_mut().push(subset);
+
+        let card = set.parse("TG05").unwrap();
+        let plan = SortPlan::new(&collection, [card]);
+        assert_eq!(
+            plan.groups()[0].describe(|card| set.label(card)),
+            "Binder 1, pages 2–3: page 2 slot 5 (#TG05)"
+        );
+    }
+
+    #[test]
+    fn test_split_card_list() {
+        let tokens: Vec<&str> = split_card_list("1, 2;TG05\n 7  ").collect();
+        assert_eq!(tokens, ["1", "2", "TG05", "7"]);
+    }
+}